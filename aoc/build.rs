@@ -0,0 +1,43 @@
+// Generates the runner's DAYS list from the `dayN` entries in aoc/Cargo.toml's [dependencies], so a
+// new day only has to be added there. Day crates in the workspace that aren't dependencies get a warning
+use std::{env, fs, path::Path};
+
+fn day_number(name: &str) -> Option<u8> {
+    name.strip_prefix("day")?.parse().ok()
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_path = Path::new(&manifest_dir).join("Cargo.toml");
+    let workspace = Path::new(&manifest_dir).parent().unwrap();
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+    println!("cargo:rerun-if-changed={}", workspace.display());
+
+    let manifest: toml::Table = fs::read_to_string(&manifest_path)
+        .unwrap()
+        .parse()
+        .expect("aoc/Cargo.toml is valid TOML");
+    let dependencies = manifest
+        .get("dependencies")
+        .and_then(|d| d.as_table())
+        .expect("aoc/Cargo.toml has a [dependencies] table");
+    let mut days: Vec<u8> = dependencies
+        .keys()
+        .filter_map(|name| day_number(name))
+        .collect();
+    days.sort_unstable();
+
+    for entry in fs::read_dir(workspace).unwrap().flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if let Some(number) = day_number(&name) {
+            if !days.contains(&number) && entry.path().join("Cargo.toml").exists() {
+                println!("cargo:warning={name} isn't in the aoc dependencies, so it can't be run");
+            }
+        }
+    }
+
+    let entries: String = days.iter().map(|n| format!("    day{n}::DAY,\n")).collect();
+    let source = format!("const DAYS: [Day; {}] = [\n{entries}];\n", days.len());
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out, source).unwrap();
+}
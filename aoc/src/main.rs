@@ -0,0 +1,328 @@
+use clap::{Parser, Subcommand};
+use day17::{load_program, Debugger, MiniPC, Register, StopReason};
+use itertools::Itertools;
+use shared::{read_input_file, Day, ParseError, Part, ALL_PARTS};
+use std::{
+    fmt::Display,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+// Every day linked into the runner, generated by build.rs from the `dayN` dependencies in Cargo.toml
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2024 solutions runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, a range of days or all of them, and print a table of answers and timings
+    Run {
+        /// Day to run, either a single day (`7`) or an inclusive range (`3-9`). Runs all days if omitted
+        #[arg(long, value_parser = parse_day_range)]
+        day: Option<RangeInclusive<u8>>,
+        /// Only run one part (`a` or `b`)
+        #[arg(long, value_parser = parse_part)]
+        part: Option<Part>,
+        /// Input file to use instead of the day's own input.txt, only valid for a single day
+        #[arg(long)]
+        input: Option<String>,
+        /// Record an animation of the part (A unless --part is given) instead of solving, only valid for a single day.
+        /// A `.gif` path writes an animated GIF, any other path is made into a directory of numbered BMP frames
+        #[arg(long, value_name = "PATH")]
+        visualize: Option<PathBuf>,
+    },
+    /// List all registered days and their default inputs
+    List,
+    /// Disassemble a day 17 program and step through it, printing the registers as it goes
+    Trace {
+        /// Program to load instead of day 17's own input.txt, `.asm` files are assembled first
+        #[arg(long)]
+        input: Option<String>,
+        /// Start with this value in register A instead of the one in the input
+        #[arg(long)]
+        a: Option<u64>,
+        /// Only print the state when about to run the instruction at this address, can be repeated
+        #[arg(long = "break", value_name = "ADDRESS")]
+        breakpoints: Vec<usize>,
+        /// Only print the state when this register (`a`, `b` or `c`) changes, can be repeated
+        #[arg(long, value_parser = parse_register)]
+        watch: Vec<Register>,
+        /// Give up after running this many instructions
+        #[arg(long, default_value_t = 10_000)]
+        max_steps: usize,
+        /// Print the listing without running anything
+        #[arg(long)]
+        disassemble: bool,
+    },
+    /// Explain which of day 5's ordering rules each update breaks, and the fewest moves that fix it
+    Rules {
+        /// Input to check instead of day 5's own input.txt
+        #[arg(long)]
+        input: Option<String>,
+        /// Also list the updates that are already in order
+        #[arg(long)]
+        all: bool,
+    },
+    /// Assemble day 17 MiniPC source into the puzzle's input format
+    Assemble {
+        /// Mnemonic source, see `trace --disassemble` for the syntax
+        source: String,
+        /// File to write instead of printing it
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn parse_day_range(arg: &str) -> Result<RangeInclusive<u8>, String> {
+    let parse_day = |s: &str| {
+        s.trim()
+            .parse::<u8>()
+            .map_err(|e| format!("Invalid day `{s}`: {e}"))
+    };
+    let range = match arg.split_once('-') {
+        Some((start, end)) => parse_day(start)?..=parse_day(end)?,
+        None => {
+            let day = parse_day(arg)?;
+            day..=day
+        }
+    };
+    if range.is_empty() {
+        return Err(format!("Day range `{arg}` is empty"));
+    }
+    Ok(range)
+}
+fn parse_part(arg: &str) -> Result<Part, String> {
+    match arg {
+        "a" | "A" => Ok(Part::A),
+        "b" | "B" => Ok(Part::B),
+        _ => Err(format!("Invalid part `{arg}`, expected `a` or `b`")),
+    }
+}
+
+fn parse_register(arg: &str) -> Result<Register, String> {
+    arg.parse().map_err(|e: ParseError| e.to_string())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            visualize,
+        } => {
+            let selected: Vec<&Day> = DAYS
+                .iter()
+                .filter(|d| day.as_ref().is_none_or(|range| range.contains(&d.number)))
+                .collect();
+            if selected.is_empty() {
+                eprintln!("No registered days match the selection");
+                std::process::exit(1);
+            }
+            if input.is_some() && selected.len() > 1 {
+                eprintln!("--input can only be used when running a single day");
+                std::process::exit(1);
+            }
+            if let Some(output) = visualize {
+                if selected.len() > 1 {
+                    eprintln!("--visualize can only be used when running a single day");
+                    std::process::exit(1);
+                }
+                let day = selected[0];
+                let input = input.as_deref().unwrap_or(day.default_input);
+                visualize_day(day, part.unwrap_or(Part::A), input, &output);
+                return;
+            }
+            let parts: Vec<Part> = match part {
+                Some(p) => vec![p],
+                None => ALL_PARTS.to_vec(),
+            };
+            run_days(&selected, &parts, input.as_deref());
+        }
+        Command::List => {
+            for day in DAYS.iter() {
+                println!("Day {:>2}  {}", day.number, day.default_input);
+            }
+        }
+        Command::Trace {
+            input,
+            a,
+            breakpoints,
+            watch,
+            max_steps,
+            disassemble,
+        } => {
+            let input = input.as_deref().unwrap_or(day17::DAY.default_input);
+            let mut machine = load_program_or_exit(input);
+            if let Some(a) = a {
+                machine.set_register(Register::A, a);
+            }
+            print!("{}", machine.listing());
+            if !disassemble {
+                trace_program(machine, &breakpoints, &watch, max_steps);
+            }
+        }
+        Command::Rules { input, all } => {
+            let input = input.as_deref().unwrap_or(day5::DAY.default_input);
+            let updates = read_input_file(input)
+                .and_then(|contents| day5::Input::parse(&contents))
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", e.with_file(input));
+                    std::process::exit(1);
+                });
+            explain_updates(&updates, all);
+        }
+        Command::Assemble { source, output } => {
+            let program = load_program_or_exit(&source).to_puzzle_input();
+            match output {
+                Some(path) => {
+                    if let Err(e) = std::fs::write(&path, program) {
+                        eprintln!("Failed to write {}: {e}", path.display());
+                        std::process::exit(1);
+                    }
+                }
+                None => print!("{program}"),
+            }
+        }
+    }
+}
+
+fn run_days(days: &[&Day], parts: &[Part], input_override: Option<&str>) {
+    print_row("Day", "Part", "Answer", "Time");
+    println!("{}", "-".repeat(3 + 2 + 5 + 2 + 20 + 2 + 12));
+    let mut total_time = Duration::ZERO;
+    for day in days {
+        let input = input_override.unwrap_or(day.default_input);
+        let contents = match read_input_file(input) {
+            Ok(contents) => contents,
+            Err(e) => {
+                print_row(day.number, "-", e, "-");
+                continue;
+            }
+        };
+        match (day.run)(&contents, parts) {
+            Ok(run) => {
+                total_time += run.parse_time;
+                print_row(day.number, "parse", "", format!("{:.2?}", run.parse_time));
+                for (part, answer, elapsed) in run.answers {
+                    total_time += elapsed;
+                    print_row(day.number, part, answer, format!("{elapsed:.2?}"));
+                }
+            }
+            Err(e) => print_row(day.number, "parse", e.with_file(input), "-"),
+        }
+    }
+    println!("{}", "-".repeat(3 + 2 + 5 + 2 + 20 + 2 + 12));
+    println!("Total time {total_time:.2?}");
+}
+fn visualize_day(day: &Day, part: Part, input: &str, output: &Path) {
+    let result = read_input_file(input)
+        .map_err(|e| e.into())
+        .and_then(|contents| (day.visualize)(&contents, part, output));
+    match result {
+        Ok(Some(frames)) => println!("Wrote {frames} frames to {}", output.display()),
+        Ok(None) => {
+            eprintln!("Day {} has no visualisation for part {part}", day.number);
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to visualise day {}: {e}", day.number);
+            std::process::exit(1);
+        }
+    }
+}
+fn load_program_or_exit(path: &str) -> MiniPC {
+    load_program(path).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    })
+}
+fn trace_program(machine: MiniPC, breakpoints: &[usize], watches: &[Register], max_steps: usize) {
+    let mut debugger = Debugger::new(machine);
+    breakpoints
+        .iter()
+        .for_each(|&address| debugger.break_at(address));
+    watches
+        .iter()
+        .for_each(|&register| debugger.watch(register));
+    // With nothing to stop on every step gets printed, otherwise only the stops
+    let print_every_step = breakpoints.is_empty() && watches.is_empty();
+    println!();
+    println!(
+        "{:>6}  {:>4}  {:<8}  {:>16}  {:>16}  {:>16}  Out",
+        "Step", "Addr", "Instr", "A", "B", "C"
+    );
+    let reason = loop {
+        let (trace, reason) = debugger.run(max_steps - debugger.steps());
+        let shown = if print_every_step {
+            &trace[..]
+        } else {
+            &trace[trace.len().saturating_sub(1)..]
+        };
+        for step in shown {
+            let [a, b, c] = step.registers;
+            let output = step.output.map(|o| o.to_string()).unwrap_or_default();
+            println!(
+                "{:>6}  {:>4}  {:<8}  {a:>16}  {b:>16}  {c:>16}  {output}",
+                step.step,
+                step.instruction.address,
+                step.instruction.to_string()
+            );
+        }
+        match reason {
+            StopReason::Breakpoint(address) => println!("Break before {address}"),
+            StopReason::Watch(register, old, new) => println!("{register} changed {old} -> {new}"),
+            StopReason::Halted | StopReason::StepLimit | StopReason::Error(_) => break reason,
+        }
+    };
+    match reason {
+        StopReason::StepLimit => println!("Stopped after {max_steps} steps"),
+        StopReason::Error(e) => println!("Failed after {} steps: {e}", debugger.steps()),
+        _ => println!("Halted after {} steps", debugger.steps()),
+    }
+    println!("Output {}", debugger.machine().output().iter().format(","));
+}
+fn explain_updates(updates: &day5::Input, show_all: bool) {
+    let graph = updates.graph();
+    let mut broken = 0;
+    for (number, seq) in updates.sequences().iter().enumerate() {
+        let violations = graph.violations(seq);
+        if violations.is_empty() && !show_all {
+            continue;
+        }
+        println!("Update {}: {}", number + 1, seq.iter().format(","));
+        if violations.is_empty() {
+            println!("  In order");
+            continue;
+        }
+        broken += 1;
+        for violation in &violations {
+            println!("  {violation}");
+        }
+        match graph.repair_moves(seq) {
+            Ok(moves) => {
+                let mut fixed = seq.clone();
+                for repair in &moves {
+                    println!("  {repair}");
+                    repair.apply(&mut fixed);
+                }
+                println!("  Fixed: {}", fixed.iter().format(","));
+            }
+            Err(e) => println!("  Can't be fixed. {e}"),
+        }
+    }
+    println!(
+        "{broken} of {} updates break the rules",
+        updates.sequences().len()
+    );
+}
+fn print_row(day: impl Display, part: impl Display, answer: impl Display, time: impl Display) {
+    println!("{day:>3}  {part:<5}  {answer:<20}  {time:>12}");
+}
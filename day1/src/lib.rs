@@ -1,14 +1,26 @@
 use itertools::Itertools;
+use shared::{Answer, ParseError, Solution};
 
-fn main() {
-    part_a();
-    part_b();
-}
+shared::register_day!(1, Day1);
+
+pub struct Day1;
+impl Solution for Day1 {
+    type Input = Vec<Vec<i64>>;
+    type Config = ();
 
-fn part_a() -> i64 {
-    let mut input = shared::read_whitespace_separated_numbers_by_column("input.txt", 2).unwrap();
-    assert_eq!(input[0].len(), input[1].len());
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        shared::parse_whitespace_separated_numbers_by_column(input, 2)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+}
 
+fn part_a(input: &[Vec<i64>]) -> i64 {
+    let mut input = input.to_vec();
     input[0].sort();
     input[1].sort();
     //Elements are sorted small -> large
@@ -18,13 +30,10 @@ fn part_a() -> i64 {
         let distance = (a - b).abs();
         distance_total += distance;
     }
-    println!("PART A: Total distance: {}", distance_total);
     distance_total
 }
-fn part_b() -> i64 {
+fn part_b(input: &[Vec<i64>]) -> i64 {
     let (slice_0, slice_1) = {
-        let input = shared::read_whitespace_separated_numbers_by_column("input.txt", 2).unwrap();
-        assert_eq!(input[0].len(), input[1].len());
         let mut slice_0 = input[0].clone();
         let mut slice_1 = input[1].clone();
         slice_0.sort();
@@ -47,22 +56,22 @@ fn part_b() -> i64 {
         .zip(counters.iter())
         .map(|(a, b)| a * b)
         .sum::<i64>();
-    println!("PART B: Total distance: {}", total);
     total
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared::Part;
 
     #[test]
     fn test_part_a() {
-        let results = part_a();
+        let results = Day1::solve_file("input.txt", Part::A);
         assert_eq!(results, 1579939);
     }
     #[test]
     fn test_part_b() {
-        let results = part_b();
+        let results = Day1::solve_file("input.txt", Part::B);
         assert_eq!(results, 20351745);
     }
 }
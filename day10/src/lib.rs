@@ -0,0 +1,130 @@
+use shared::{parse_num_grid, Answer, Grid, ParseError, Solution};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone)]
+pub struct TopoHeightMap {
+    map: Grid<i64>,
+}
+impl TopoHeightMap {
+    pub fn get_trail_heads(&self) -> Vec<(usize, usize)> {
+        //Find the x/y position of all trail heads
+        self.map.find_all(&0).collect()
+    }
+
+    // Returns count of ends reached
+    pub fn walk_uphill_to_end(
+        &self,
+        position: (usize, usize),
+        mut history: HashSet<(usize, usize)>,
+        ends_seen: &mut HashSet<(usize, usize)>,
+        filter_distinct: bool,
+    ) -> i64 {
+        let current_position_height = self.map[position];
+        let target_height = current_position_height + 1;
+        if !history.insert(position) {
+            return 0; // Have been here before, dont step backwards
+        }
+        self.map
+            .neighbours4(position)
+            .map(|point| {
+                let point_height = self.map[point];
+
+                if point_height == target_height {
+                    if point_height == 9 {
+                        // At the end target
+                        // println!("Got to the end via {history:?}");
+                        if filter_distinct {
+                            if ends_seen.insert(point) {
+                                1
+                            } else {
+                                0
+                            }
+                        } else {
+                            1
+                        }
+                    } else {
+                        //Recurse
+                        // println!(
+                        //     "Point test {point:?} = {}",
+                        //     self.map.get(point.0, point.1).unwrap()
+                        // );
+                        self.walk_uphill_to_end(point, history.clone(), ends_seen, filter_distinct)
+                    }
+                } else {
+                    // Not able to walk uphill here
+                    0
+                }
+            })
+            .sum()
+    }
+}
+shared::register_day!(10, Day10);
+
+pub struct Day10;
+impl Solution for Day10 {
+    type Input = TopoHeightMap;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(TopoHeightMap {
+            map: parse_num_grid(input)?,
+        })
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+}
+fn part_a(map: &TopoHeightMap) -> i64 {
+    let trail_heads = map.get_trail_heads();
+    trail_heads
+        .iter()
+        .map(|&head| {
+            let mut ends = HashSet::new();
+            let paths = map.walk_uphill_to_end(head, HashSet::new(), &mut ends, true);
+            // println!("Path from {head:?} has {paths} ends -> {ends:?}");
+            paths
+        })
+        .sum()
+}
+fn part_b(map: &TopoHeightMap) -> i64 {
+    let trail_heads = map.get_trail_heads();
+    trail_heads
+        .iter()
+        .map(|&head| {
+            let mut ends = HashSet::new();
+            let paths = map.walk_uphill_to_end(head, HashSet::new(), &mut ends, false);
+            // println!("Path from {head:?} has {paths} ends -> {ends:?}");
+            paths
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day10::solve_file("test.txt", Part::A);
+        assert_eq!(results, 36);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day10::solve_file("input.txt", Part::A);
+        assert_eq!(results, 624);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day10::solve_file("test.txt", Part::B);
+        assert_eq!(results, 81);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day10::solve_file("input.txt", Part::B);
+        assert_eq!(results, 1483);
+    }
+}
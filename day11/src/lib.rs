@@ -0,0 +1,279 @@
+use count_digits::CountDigits;
+use shared::{Answer, ParseError, Solution};
+use std::collections::HashMap;
+
+mod rules;
+pub use rules::*;
+
+shared::register_day!(11, Day11);
+
+pub struct Day11;
+impl Solution for Day11 {
+    type Input = Vec<Stone>;
+    type Config = Day11Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input
+            .split_whitespace()
+            .map(|l| l.parse())
+            .collect::<Result<_, _>>()?)
+    }
+    fn part_a(input: &Self::Input, config: &Day11Config) -> Answer {
+        match blink_to_count(input, config.blinks_a, &config.rules) {
+            Ok(count) => count.into(),
+            Err(e) => e.to_string().into(),
+        }
+    }
+    fn part_b(input: &Self::Input, config: &Day11Config) -> Answer {
+        match blink_to_count(input, config.blinks_b, &config.rules) {
+            Ok(count) => count.into(),
+            Err(e) => e.to_string().into(),
+        }
+    }
+}
+pub struct Day11Config {
+    pub rules: RuleSet, // See `RuleSet::load` for trying out other rules from a file
+    pub blinks_a: usize,
+    pub blinks_b: usize,
+}
+impl Default for Day11Config {
+    fn default() -> Self {
+        Self {
+            rules: RuleSet::default(),
+            blinks_a: 25,
+            blinks_b: 75,
+        }
+    }
+}
+
+pub type Stone = u64;
+
+// Perform a rule update, if a new stone is made return it
+pub fn blink(stone: &mut Stone) -> Result<Option<Stone>, BlinkError> {
+    //Rule 0
+    if *stone == 0 {
+        *stone = 1;
+        return Ok(None);
+    }
+    //Rule 1
+    let num_digits = stone.count_digits();
+    if num_digits.is_multiple_of(2) {
+        //Event number, split in half, this stone gets the first half and return a new stone with second half
+        let left = *stone / 10u64.pow((num_digits / 2) as u32);
+        let right = *stone % 10u64.pow((num_digits / 2) as u32);
+        let new_stone = right;
+        *stone = left;
+        return Ok(Some(new_stone));
+    }
+    // (final) Rule 2
+    *stone = stone
+        .checked_mul(2024)
+        .ok_or_else(|| BlinkError::StoneOverflow {
+            stone: *stone,
+            rule: "* => * 2024".to_owned(),
+        })?;
+    Ok(None)
+}
+
+// Counts stones by value rather than keeping them in order, as the same values keep coming up and
+// every stone with a value turns into the same stones. Each blink only has to look at the distinct values
+pub struct StoneCounter<R> {
+    rule: R,
+    stones: HashMap<Stone, usize>,
+    growth: Vec<usize>, // How many stones there were after each blink, starting from none
+}
+impl<R: BlinkRule> StoneCounter<R> {
+    // Takes `blink` itself, anything else shaped like it, or a `RuleSet`
+    pub fn new(stones: &[Stone], rule: R) -> Self {
+        let mut counts = HashMap::new();
+        for &stone in stones {
+            *counts.entry(stone).or_default() += 1;
+        }
+        Self {
+            rule,
+            stones: counts,
+            growth: vec![stones.len()],
+        }
+    }
+    // Stones are left as they were if a value or count overflows, values go through smallest first so
+    // it's always the same one reported
+    pub fn blink(&mut self) -> Result<(), BlinkError> {
+        let mut next: HashMap<Stone, usize> = HashMap::with_capacity(self.stones.len() * 2);
+        let mut total: usize = 0;
+        let mut values: Vec<Stone> = self.stones.keys().copied().collect();
+        values.sort_unstable();
+        for value in values {
+            let count = self.stones[&value];
+            let mut stone = value;
+            let new_stone = self.rule.apply(&mut stone)?;
+            let mut add = |stone: Stone| {
+                let entry = next.entry(stone).or_default();
+                *entry = entry.checked_add(count)?;
+                total = total.checked_add(count)?;
+                Some(())
+            };
+            new_stone
+                .map_or(Some(()), &mut add)
+                .and_then(|_| add(stone))
+                .ok_or_else(|| BlinkError::CountOverflow {
+                    stone: value,
+                    rule: self.rule.describe(value),
+                })?;
+        }
+        self.stones = next;
+        self.growth.push(total);
+        Ok(())
+    }
+    pub fn blink_times(&mut self, blinks: usize) -> Result<(), BlinkError> {
+        for _ in 0..blinks {
+            self.blink()?;
+        }
+        Ok(())
+    }
+    pub fn blinks(&self) -> usize {
+        self.growth.len() - 1
+    }
+    pub fn total(&self) -> usize {
+        self.growth[self.growth.len() - 1]
+    }
+    pub fn distinct(&self) -> usize {
+        self.stones.len()
+    }
+    // How many of each value there are
+    pub fn stones(&self) -> &HashMap<Stone, usize> {
+        &self.stones
+    }
+    pub fn growth(&self) -> &[usize] {
+        &self.growth
+    }
+}
+
+pub fn blink_to_count(
+    stones: &[Stone],
+    blinks: usize,
+    rules: &RuleSet,
+) -> Result<usize, BlinkError> {
+    let mut counter = StoneCounter::new(stones, rules);
+    counter.blink_times(blinks)?;
+    Ok(counter.total())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day11::solve_file("test.txt", Part::A);
+        assert_eq!(results, 55312);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day11::solve_file("input.txt", Part::A);
+        assert_eq!(results, 233875);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day11::solve_file("test.txt", Part::B);
+        assert_eq!(results, 65601038650482);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day11::solve_file("input.txt", Part::B);
+        assert_eq!(results, 277444936413293);
+    }
+    #[test]
+    fn test_stone_counter() {
+        let mut counter = StoneCounter::new(&[125, 17], blink);
+        counter.blink_times(6).unwrap();
+        assert_eq!(counter.blinks(), 6);
+        assert_eq!(counter.growth(), [2, 3, 4, 5, 9, 13, 22]);
+        // The stones the puzzle lists after 6 blinks, in any order
+        let mut expected: HashMap<Stone, usize> = HashMap::new();
+        for stone in [
+            2097446912, 14168, 4048, 2, 0, 2, 4, 40, 48, 2024, 40, 48, 80, 96, 2, 8, 6, 7, 6, 0, 3,
+            2,
+        ] {
+            *expected.entry(stone).or_default() += 1;
+        }
+        assert_eq!(counter.stones(), &expected);
+        assert_eq!(counter.distinct(), 15);
+    }
+    #[test]
+    fn test_blink_overflow() {
+        // Odd digits, so it gets multiplied
+        let mut stone = u64::MAX / 1000;
+        assert_eq!(stone.count_digits(), 17);
+        assert_eq!(
+            blink(&mut stone),
+            RuleSet::default().apply(&mut stone.clone())
+        );
+        assert_eq!(
+            blink(&mut stone).unwrap_err().to_string(),
+            format!(
+                "Stone {} overflowed a u64 under `* => * 2024`",
+                u64::MAX / 1000
+            )
+        );
+        assert_eq!(stone, u64::MAX / 1000);
+    }
+    #[test]
+    fn test_custom_rules() {
+        // Every stone splits into two of one less, until they hit 0
+        let halve = |stone: &mut Stone| {
+            if *stone == 0 {
+                return Ok(None);
+            }
+            *stone -= 1;
+            Ok(Some(*stone))
+        };
+        let mut counter = StoneCounter::new(&[3], halve);
+        counter.blink_times(5).unwrap();
+        assert_eq!(counter.growth(), [1, 2, 4, 8, 8, 8]);
+        assert_eq!(counter.stones(), &HashMap::from([(0, 8)]));
+    }
+    #[test]
+    fn test_count_overflow() {
+        // Doubles every other blink, the values stay small but the count doesn't
+        let rules = RuleSet::parse("stone < 10 => * 11\n* => split").unwrap();
+        let mut counter = StoneCounter::new(&[5], &rules);
+        assert_eq!(
+            counter.blink_times(200).unwrap_err().to_string(),
+            "Stone count overflowed a usize when 55 blinked under `* => split`"
+        );
+        assert_eq!(counter.total(), 1 << (usize::BITS - 1));
+        let mut counter = StoneCounter::new(&[1], |stone: &mut Stone| Ok(Some(*stone)));
+        assert_eq!(
+            counter.blink_times(100),
+            Err(BlinkError::CountOverflow {
+                stone: 1,
+                rule: "custom rule".to_owned()
+            })
+        );
+        assert_eq!(counter.blinks(), usize::BITS as usize - 1);
+    }
+    #[test]
+    fn test_rule_files() {
+        let config = Day11Config {
+            rules: RuleSet::load("rules.txt").unwrap(),
+            ..Default::default()
+        };
+        let results = Day11::solve_file_with("test.txt", Part::A, &config);
+        assert_eq!(results, 55312);
+
+        // Without splitting, stones only ever get bigger until they don't fit
+        let config = Day11Config {
+            rules: RuleSet::parse("stone == 0 => = 1\n* => * 2024").unwrap(),
+            ..Default::default()
+        };
+        let results = Day11::solve_file_with("test.txt", Part::A, &config);
+        assert_eq!(
+            results,
+            "Stone 577432816857284608 overflowed a u64 under `* => * 2024`"
+        );
+        let mut counter = StoneCounter::new(&[125, 17], &config.rules);
+        assert!(counter.blink_times(25).is_err());
+        assert_eq!(counter.blinks(), 5);
+    }
+}
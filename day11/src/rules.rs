@@ -0,0 +1,398 @@
+// Stone rules written out as text, one `predicate => transformation` per line, the first match wins
+//
+//     # The puzzle's own rules
+//     stone == 0      => = 1
+//     digits % 2 == 0 => split
+//     *               => * 2024
+//
+// Predicates compare `stone` or `digits` (optionally `% n` first) with ==, !=, <, <=, > or >=, and `*`
+// matches anything. Transformations are `split`, which halves the digits into two stones (and is an
+// error for an odd number of digits, so guard it with `digits % 2 == 0`), or steps applied in order from `= n`, `+ n`, `- n`, `* n` and `/ n`. Stones no rule matches stay as they are
+use std::{cmp::Ordering, fmt::Display};
+
+use count_digits::CountDigits;
+use shared::{numbered_lines, read_input_file, ParseError};
+
+use crate::Stone;
+
+pub const PUZZLE_RULES: &str = "stone == 0 => = 1
+digits % 2 == 0 => split
+* => * 2024";
+
+// Blinking went somewhere a u64 or usize can't follow, rather than quietly wrapping round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlinkError {
+    StoneOverflow { stone: Stone, rule: String },
+    CountOverflow { stone: Stone, rule: String }, // Too many stones to count
+    OddSplit { stone: Stone, rule: String },      // No middle to split the digits at
+}
+impl Display for BlinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlinkError::StoneOverflow { stone, rule } => {
+                write!(f, "Stone {stone} overflowed a u64 under `{rule}`")
+            }
+            BlinkError::CountOverflow { stone, rule } => write!(
+                f,
+                "Stone count overflowed a usize when {stone} blinked under `{rule}`"
+            ),
+            BlinkError::OddSplit { stone, rule } => write!(
+                f,
+                "Stone {stone} has an odd number of digits, so `{rule}` can't split it"
+            ),
+        }
+    }
+}
+
+// One blink for one stone, updating it and returning any new stone, the way `blink` does
+pub trait BlinkRule {
+    fn apply(&self, stone: &mut Stone) -> Result<Option<Stone>, BlinkError>;
+    // The rule the stone blinks under, for errors
+    fn describe(&self, _stone: Stone) -> String {
+        "custom rule".to_owned()
+    }
+}
+impl<F> BlinkRule for F
+where
+    F: Fn(&mut Stone) -> Result<Option<Stone>, BlinkError>,
+{
+    fn apply(&self, stone: &mut Stone) -> Result<Option<Stone>, BlinkError> {
+        self(stone)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    Stone,
+    Digits,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Predicate {
+    Always,
+    Compare {
+        operand: Operand,
+        modulus: Option<u64>,
+        accepted: [bool; 3], // Whether less, equal and greater match
+        value: u64,
+    },
+}
+impl Predicate {
+    fn matches(&self, stone: Stone) -> bool {
+        match *self {
+            Predicate::Always => true,
+            Predicate::Compare {
+                operand,
+                modulus,
+                accepted,
+                value,
+            } => {
+                let mut lhs = match operand {
+                    Operand::Stone => stone,
+                    Operand::Digits => stone.count_digits() as u64,
+                };
+                if let Some(modulus) = modulus {
+                    lhs %= modulus;
+                }
+                let ordering = match lhs.cmp(&value) {
+                    Ordering::Less => 0,
+                    Ordering::Equal => 1,
+                    Ordering::Greater => 2,
+                };
+                accepted[ordering]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Set(u64),
+    Add(u64),
+    Subtract(u64),
+    Multiply(u64),
+    Divide(u64),
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Transformation {
+    Split,
+    Steps(Vec<Step>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    text: String, // As written, for error messages
+    predicate: Predicate,
+    transformation: Transformation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::parse(PUZZLE_RULES).expect("Puzzle rules are valid")
+    }
+}
+impl RuleSet {
+    pub fn load(path: &str) -> Result<Self, ParseError> {
+        Self::parse(&read_input_file(path)?).map_err(|e| e.with_file(path))
+    }
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut rules = Vec::new();
+        for (line_number, line) in numbered_lines(source) {
+            let text = line.split('#').next().unwrap_or_default().trim();
+            if text.is_empty() {
+                continue;
+            }
+            let column = line.find(text).unwrap_or_default() + 1;
+            let (predicate, transformation) = text.split_once("=>").ok_or_else(|| {
+                ParseError::at(
+                    line_number,
+                    column,
+                    text,
+                    "Expected `predicate => transformation`",
+                )
+            })?;
+            let transformation_column = column
+                + predicate.len()
+                + 2
+                + (transformation.len() - transformation.trim_start().len());
+            rules.push(Rule {
+                text: text.to_owned(),
+                predicate: parse_predicate(predicate).map_err(|message| {
+                    ParseError::at(line_number, column, predicate.trim(), message)
+                })?,
+                transformation: parse_transformation(transformation).map_err(|message| {
+                    ParseError::at(
+                        line_number,
+                        transformation_column,
+                        transformation.trim(),
+                        message,
+                    )
+                })?,
+            });
+        }
+        Ok(Self { rules })
+    }
+}
+impl RuleSet {
+    fn rule_for(&self, stone: Stone) -> Option<&Rule> {
+        self.rules.iter().find(|r| r.predicate.matches(stone))
+    }
+}
+impl BlinkRule for RuleSet {
+    fn apply(&self, stone: &mut Stone) -> Result<Option<Stone>, BlinkError> {
+        let Some(rule) = self.rule_for(*stone) else {
+            return Ok(None);
+        };
+        let overflow = || BlinkError::StoneOverflow {
+            stone: *stone,
+            rule: rule.text.clone(),
+        };
+        match &rule.transformation {
+            Transformation::Split => {
+                let digits = stone.count_digits();
+                if digits % 2 == 1 {
+                    return Err(BlinkError::OddSplit {
+                        stone: *stone,
+                        rule: rule.text.clone(),
+                    });
+                }
+                // This stone keeps the first half and the new one gets the second
+                let shift = 10u64.pow((digits / 2) as u32);
+                let new_stone = *stone % shift;
+                *stone /= shift;
+                Ok(Some(new_stone))
+            }
+            Transformation::Steps(steps) => {
+                let mut value = *stone;
+                for step in steps {
+                    value = match *step {
+                        Step::Set(n) => Some(n),
+                        Step::Add(n) => value.checked_add(n),
+                        Step::Subtract(n) => value.checked_sub(n),
+                        Step::Multiply(n) => value.checked_mul(n),
+                        Step::Divide(n) => value.checked_div(n),
+                    }
+                    .ok_or_else(overflow)?;
+                }
+                *stone = value;
+                Ok(None)
+            }
+        }
+    }
+    fn describe(&self, stone: Stone) -> String {
+        self.rule_for(stone)
+            .map_or("no rule".to_owned(), |rule| rule.text.clone())
+    }
+}
+// So counters can borrow the rules from a config
+impl BlinkRule for &RuleSet {
+    fn apply(&self, stone: &mut Stone) -> Result<Option<Stone>, BlinkError> {
+        (*self).apply(stone)
+    }
+    fn describe(&self, stone: Stone) -> String {
+        (*self).describe(stone)
+    }
+}
+
+fn parse_number(digits: &str) -> Result<u64, String> {
+    digits
+        .parse()
+        .map_err(|_| format!("Expected a number, found `{digits}`"))
+}
+fn parse_predicate(source: &str) -> Result<Predicate, String> {
+    let source: String = source.split_whitespace().collect();
+    if source == "*" {
+        return Ok(Predicate::Always);
+    }
+    let comparators = [
+        ("==", [false, true, false]),
+        ("!=", [true, false, true]),
+        ("<=", [true, true, false]),
+        (">=", [false, true, true]),
+        ("<", [true, false, false]),
+        (">", [false, false, true]),
+    ];
+    let (lhs, accepted, value) = comparators
+        .iter()
+        .find_map(|&(symbol, accepted)| {
+            let (lhs, value) = source.split_once(symbol)?;
+            Some((lhs, accepted, value))
+        })
+        .ok_or("Expected a comparison like `digits % 2 == 0`, or `*`")?;
+    let (operand, modulus) = match lhs.split_once('%') {
+        Some((operand, modulus)) => (operand, Some(parse_number(modulus)?)),
+        None => (lhs, None),
+    };
+    if modulus == Some(0) {
+        return Err("Can't take a remainder by 0".to_owned());
+    }
+    let operand = match operand {
+        "stone" => Operand::Stone,
+        "digits" => Operand::Digits,
+        _ => return Err(format!("Expected `stone` or `digits`, found `{operand}`")),
+    };
+    Ok(Predicate::Compare {
+        operand,
+        modulus,
+        accepted,
+        value: parse_number(value)?,
+    })
+}
+fn parse_transformation(source: &str) -> Result<Transformation, String> {
+    let source: String = source.split_whitespace().collect();
+    if source == "split" {
+        return Ok(Transformation::Split);
+    }
+    let mut steps = Vec::new();
+    let mut rest = source.as_str();
+    while let Some(op) = rest.chars().next() {
+        let start = op.len_utf8();
+        let digits_end = rest[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(rest.len(), |end| end + start);
+        let n = parse_number(&rest[start..digits_end])?;
+        steps.push(match op {
+            '=' => Step::Set(n),
+            '+' => Step::Add(n),
+            '-' => Step::Subtract(n),
+            '*' => Step::Multiply(n),
+            '/' if n == 0 => return Err("Can't divide by 0".to_owned()),
+            '/' => Step::Divide(n),
+            _ => {
+                return Err(format!(
+                    "Unknown step `{op}`, expected one of = + - * /, or split"
+                ))
+            }
+        });
+        rest = &rest[digits_end..];
+    }
+    if steps.is_empty() {
+        return Err("Missing transformation".to_owned());
+    }
+    Ok(Transformation::Steps(steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blink;
+
+    #[test]
+    fn test_puzzle_rules_match_blink() {
+        let loaded = RuleSet::load("rules.txt").unwrap();
+        for rules in [RuleSet::default(), loaded] {
+            for start in (0..2000).chain([123456, 1000, 99, 2024]) {
+                let (mut expected, mut compiled) = (start, start);
+                let new_stone = blink(&mut expected);
+                assert_eq!(rules.apply(&mut compiled), new_stone);
+                assert_eq!(compiled, expected);
+            }
+        }
+    }
+    #[test]
+    fn test_variants() {
+        let rules = RuleSet::parse(
+            "stone < 10 => * 3 + 1 # Small stones grow
+             digits % 3 != 0 => / 2
+             * => split",
+        )
+        .unwrap();
+        let blinked = |mut stone: Stone| (rules.apply(&mut stone).unwrap(), stone);
+        assert_eq!(blinked(4), (None, 13));
+        assert_eq!(blinked(20), (None, 10));
+        assert_eq!(blinked(123456), (Some(456), 123));
+        // Nothing matches, so nothing happens
+        let rules = RuleSet::parse("stone >= 10 => = 0").unwrap();
+        let mut stone = 7;
+        assert_eq!(rules.apply(&mut stone), Ok(None));
+    }
+    #[test]
+    fn test_overflow() {
+        let rules = RuleSet::parse("* => * 2024").unwrap();
+        let mut stone = u64::MAX / 2000;
+        assert_eq!(
+            rules.apply(&mut stone).unwrap_err().to_string(),
+            format!(
+                "Stone {} overflowed a u64 under `* => * 2024`",
+                u64::MAX / 2000
+            )
+        );
+        let rules = RuleSet::parse("* => - 1").unwrap();
+        assert!(rules.apply(&mut 0).is_err());
+    }
+    #[test]
+    fn test_odd_split() {
+        let rules = RuleSet::parse("* => split").unwrap();
+        let mut stone = 5;
+        assert_eq!(
+            rules.apply(&mut stone).unwrap_err().to_string(),
+            "Stone 5 has an odd number of digits, so `* => split` can't split it"
+        );
+        assert_eq!(stone, 5);
+        let mut stone = 123;
+        assert!(rules.apply(&mut stone).is_err());
+        let mut stone = 1234;
+        assert_eq!(rules.apply(&mut stone), Ok(Some(34)));
+        assert_eq!(stone, 12);
+    }
+    #[test]
+    fn test_parse_errors() {
+        let error = |source: &str| RuleSet::parse(source).unwrap_err().to_string();
+        assert_eq!(
+            error("stone == 0"),
+            "1:1: Expected `predicate => transformation` (found `stone == 0`)"
+        );
+        assert_eq!(
+            error("# Comment\n  weight == 0 => = 1"),
+            "2:3: Expected `stone` or `digits`, found `weight` (found `weight == 0`)"
+        );
+        assert_eq!(
+            error("* => % 3"),
+            "1:6: Unknown step `%`, expected one of = + - * /, or split (found `% 3`)"
+        );
+        assert_eq!(error("* => / 0"), "1:6: Can't divide by 0 (found `/ 0`)");
+    }
+}
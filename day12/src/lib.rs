@@ -0,0 +1,61 @@
+use shared::{parse_grid, regions::label_regions, Answer, Grid, ParseError, Solution};
+
+shared::register_day!(12, Day12);
+
+pub struct Day12;
+impl Solution for Day12 {
+    type Input = Grid<u8>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+}
+fn part_a(grid: &Grid<u8>) -> i64 {
+    // Find all regions in the matrix
+    label_regions(grid)
+        .regions
+        .iter()
+        .map(|r| r.area() * r.perimeter())
+        .sum::<usize>() as i64
+}
+fn part_b(grid: &Grid<u8>) -> i64 {
+    label_regions(grid)
+        .regions
+        .iter()
+        .map(|r| r.area() * r.sides())
+        .sum::<usize>() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day12::solve_file("test.txt", Part::A);
+        assert_eq!(results, 1930);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day12::solve_file("input.txt", Part::A);
+        assert_eq!(results, 1457298);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day12::solve_file("test.txt", Part::B);
+        assert_eq!(results, 1206);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day12::solve_file("input.txt", Part::B);
+        assert_eq!(results, 921636);
+    }
+}
@@ -0,0 +1,272 @@
+// Exact integer solving for two buttons, everything in i128 so nothing rounds or overflows
+//
+//     a_x*A + b_x*B = Z_x
+//     a_y*A + b_y*B = Z_y
+//
+// When the buttons aren't collinear there's exactly one answer, from Cramer's rule, and it only counts
+// if it comes out whole. When they are, both equations say the same thing (or contradict each other),
+// and the whole number answers to the one equation lie along a line that the extended GCD gives us.
+// The cost is linear along that line, so the cheapest answer is at one end of the part that's allowed
+
+// Cheapest (A, B) presses, None if the prize can't be reached
+pub fn solve_two_buttons(
+    a: (i64, i64),
+    b: (i64, i64),
+    target: (i64, i64),
+    costs: (i64, i64),
+    press_limit: Option<i64>,
+) -> Option<(i64, i64)> {
+    let (ax, ay, bx, by) = (a.0 as i128, a.1 as i128, b.0 as i128, b.1 as i128);
+    let (tx, ty) = (target.0 as i128, target.1 as i128);
+    let determinant = ax * by - ay * bx;
+    let (a_presses, b_presses) = if determinant != 0 {
+        let a_numerator = tx * by - ty * bx;
+        let b_numerator = ax * ty - ay * tx;
+        if a_numerator % determinant != 0 || b_numerator % determinant != 0 {
+            return None;
+        }
+        (a_numerator / determinant, b_numerator / determinant)
+    } else {
+        // Use whichever equation isn't all zeros, the other is a multiple of it
+        let (ca, cb, t) = if (ax, bx) != (0, 0) {
+            (ax, bx, tx)
+        } else {
+            (ay, by, ty)
+        };
+        cheapest_on_line(ca, cb, t, (costs.0 as i128, costs.1 as i128), press_limit)?
+    };
+    let limit = press_limit.map_or(i128::MAX, |l| l as i128);
+    if !(0..=limit).contains(&a_presses) || !(0..=limit).contains(&b_presses) {
+        return None;
+    }
+    // Catches the collinear case where the equations contradict each other
+    if ax * a_presses + bx * b_presses != tx || ay * a_presses + by * b_presses != ty {
+        return None;
+    }
+    Some((a_presses as i64, b_presses as i64))
+}
+
+// Cheapest non-negative whole (A, B) with a*A + b*B = t
+fn cheapest_on_line(
+    a: i128,
+    b: i128,
+    t: i128,
+    costs: (i128, i128),
+    press_limit: Option<i64>,
+) -> Option<(i128, i128)> {
+    if (a, b) == (0, 0) {
+        // Neither button moves the claw at all
+        return (t == 0).then_some((0, 0));
+    }
+    let (g, x, y) = extended_gcd(a, b);
+    if t % g != 0 {
+        return None;
+    }
+    // Every answer is (a0 + k*step_a, b0 - k*step_b) for whole k
+    let (a0, b0) = (x * (t / g), y * (t / g));
+    let (step_a, step_b) = (b / g, a / g);
+
+    let mut range = KRange::default();
+    range.at_least_zero(a0, step_a);
+    range.at_least_zero(b0, -step_b);
+    if let Some(limit) = press_limit {
+        let limit = limit as i128;
+        range.at_least_zero(limit - a0, -step_a);
+        range.at_least_zero(limit - b0, step_b);
+    }
+    let slope = costs.0 * step_a - costs.1 * step_b;
+    let k = match (range.low, range.high) {
+        _ if range.empty => return None,
+        (Some(low), Some(high)) if low > high => return None,
+        (Some(low), _) if slope >= 0 => low,
+        (_, Some(high)) if slope <= 0 => high,
+        // Gets cheaper forever, only possible with negative costs
+        _ => return None,
+    };
+    Some((a0 + k * step_a, b0 - k * step_b))
+}
+
+// The whole numbers k satisfying a set of `constant + coefficient * k >= 0`, None for no bound
+#[derive(Default)]
+pub(crate) struct KRange {
+    pub(crate) low: Option<i128>,
+    pub(crate) high: Option<i128>,
+    pub(crate) empty: bool,
+}
+impl KRange {
+    pub(crate) fn at_least_zero(&mut self, constant: i128, coefficient: i128) {
+        match coefficient.signum() {
+            0 => self.empty |= constant < 0,
+            // k >= ceil(-constant / coefficient)
+            1 => {
+                let bound = -constant.div_euclid(coefficient);
+                self.low = Some(self.low.map_or(bound, |low| low.max(bound)));
+            }
+            // k <= floor(constant / -coefficient)
+            _ => {
+                let bound = constant.div_euclid(-coefficient);
+                self.high = Some(self.high.map_or(bound, |high| high.min(bound)));
+            }
+        }
+    }
+}
+
+// (g, x, y) with a*x + b*y = g, g positive unless both are 0
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every press count up to the limit, for checking against
+    fn brute_force(
+        a: (i64, i64),
+        b: (i64, i64),
+        target: (i64, i64),
+        costs: (i64, i64),
+        limit: i64,
+    ) -> Option<(i64, i64)> {
+        (0..=limit)
+            .flat_map(|i| (0..=limit).map(move |j| (i, j)))
+            .filter(|&(i, j)| (a.0 * i + b.0 * j, a.1 * i + b.1 * j) == target)
+            .min_by_key(|&(i, j)| (costs.0 * i + costs.1 * j, i))
+    }
+    fn cost(presses: Option<(i64, i64)>, costs: (i64, i64)) -> Option<i64> {
+        presses.map(|(i, j)| costs.0 * i + costs.1 * j)
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (-6, 4), (0, 5), (7, 0), (13, -13)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert!(g > 0);
+        }
+    }
+    #[test]
+    fn test_collinear_buttons() {
+        // B goes twice as far, so it's cheaper to press it when it fits
+        let presses = solve_two_buttons((2, 4), (4, 8), (10, 20), (3, 1), Some(100));
+        assert_eq!(presses, Some((1, 2)));
+        // When A's the better deal
+        let presses = solve_two_buttons((2, 4), (4, 8), (10, 20), (1, 5), Some(100));
+        assert_eq!(presses, Some((5, 0)));
+        // On the line but not reachable in whole presses
+        assert_eq!(
+            solve_two_buttons((2, 4), (4, 8), (7, 14), (3, 1), None),
+            None
+        );
+        // Off the line
+        assert_eq!(
+            solve_two_buttons((2, 4), (4, 8), (10, 21), (3, 1), None),
+            None
+        );
+        // The limit pushes it towards the expensive button
+        let presses = solve_two_buttons((3, 3), (5, 5), (200, 200), (3, 1), None);
+        assert_eq!(presses, Some((0, 40)));
+        let presses = solve_two_buttons((3, 3), (5, 5), (200, 200), (3, 1), Some(38));
+        assert_eq!(presses, Some((5, 37)));
+        let presses = solve_two_buttons((3, 3), (5, 5), (200, 200), (3, 1), Some(30));
+        assert_eq!(presses, Some((20, 28)));
+        assert_eq!(
+            solve_two_buttons((3, 3), (5, 5), (200, 200), (3, 1), Some(20)),
+            None
+        );
+    }
+    #[test]
+    fn test_zero_buttons() {
+        assert_eq!(
+            solve_two_buttons((0, 0), (0, 0), (0, 0), (3, 1), None),
+            Some((0, 0))
+        );
+        assert_eq!(
+            solve_two_buttons((0, 0), (0, 0), (1, 0), (3, 1), None),
+            None
+        );
+        // Only B moves anything, A is never worth pressing
+        assert_eq!(
+            solve_two_buttons((0, 0), (2, 1), (6, 3), (3, 1), None),
+            Some((0, 3))
+        );
+        // Only moving in Y
+        assert_eq!(
+            solve_two_buttons((0, 3), (0, 2), (0, 7), (3, 1), None),
+            Some((1, 2))
+        );
+    }
+    #[test]
+    fn test_big_targets_are_exact() {
+        // Close to collinear, where f64 inverses start going wrong
+        let offset = 10_000_000_000_000;
+        let (a, b) = ((99_999, 100_000), (100_000, 100_001));
+        // The only answer is -offset presses of A and offset of B
+        assert_eq!(
+            solve_two_buttons(a, b, (offset, offset), (3, 1), None),
+            None
+        );
+        let target = (
+            a.0 * 4_000_000_000 + b.0 * 6_000_000_000,
+            a.1 * 4_000_000_000 + b.1 * 6_000_000_000,
+        );
+        let (i, j) = solve_two_buttons(a, b, target, (3, 1), None).unwrap();
+        assert_eq!((i, j), (4_000_000_000, 6_000_000_000));
+        assert_eq!(
+            (
+                a.0 as i128 * i as i128 + b.0 as i128 * j as i128,
+                a.1 as i128 * i as i128 + b.1 as i128 * j as i128
+            ),
+            (target.0 as i128, target.1 as i128)
+        );
+        let target = (
+            a.0 * 123_456_789 + b.0 * 987_654_321,
+            a.1 * 123_456_789 + b.1 * 987_654_321,
+        );
+        assert_eq!(
+            solve_two_buttons(a, b, target, (3, 1), None),
+            Some((123_456_789, 987_654_321))
+        );
+    }
+    #[test]
+    fn test_matches_brute_force() {
+        let buttons = [
+            (0, 0),
+            (1, 2),
+            (2, 4),
+            (3, 1),
+            (0, 5),
+            (4, 0),
+            (6, 3),
+            (3, 6),
+        ];
+        for a in buttons {
+            for b in buttons {
+                for target in [(0, 0), (6, 12), (12, 6), (9, 9), (12, 24), (8, 0), (0, 15)] {
+                    for costs in [(3, 1), (1, 3), (2, 2)] {
+                        let expected = brute_force(a, b, target, costs, 30);
+                        let found = solve_two_buttons(a, b, target, costs, Some(30));
+                        assert_eq!(
+                            cost(found, costs),
+                            cost(expected, costs),
+                            "{a:?} {b:?} {target:?} {costs:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
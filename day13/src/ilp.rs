@@ -0,0 +1,448 @@
+// Cheapest presses for any number of buttons, as a small integer program
+//
+//     minimise   sum(cost_i * n_i)
+//     subject to sum(n_i * increment_i) = target, n_i whole and 0 <= n_i <= limit
+//
+// Branches on how often to press each button but the last two, which are then solved exactly. Only
+// press counts that leave something the other buttons can reach in whole presses are tried, and those
+// fall every so many presses apart, see `lattice_presses`. A branch is dropped when even the real
+// valued (LP) answer for the buttons left can't beat the best found so far. With only two equations
+// the LP answer always uses at most two buttons, so it's found by trying every single button and every
+// pair, no simplex needed. The LP answer is convex in the press count, so the search starts from its
+// lowest point and works outwards, stopping each way once the LP can't beat the best
+//
+// Increments and costs are never negative, which is what keeps the number of presses bounded
+use crate::{extended_gcd, solve_two_buttons, Button, KRange};
+
+pub fn cheapest_presses(
+    buttons: &[Button],
+    target: (i64, i64),
+    press_limit: Option<i64>,
+) -> Option<Vec<i64>> {
+    assert!(
+        buttons
+            .iter()
+            .all(|b| b.increment.0 >= 0 && b.increment.1 >= 0 && b.cost >= 0),
+        "Buttons can't have negative increments or costs"
+    );
+    let mut search = Search {
+        buttons,
+        press_limit,
+        presses: vec![0; buttons.len()],
+        best: None,
+    };
+    search.branch(0, (target.0 as i128, target.1 as i128), 0);
+    search.best.map(|(_, presses)| presses)
+}
+
+struct Search<'a> {
+    buttons: &'a [Button],
+    press_limit: Option<i64>,
+    presses: Vec<i64>,
+    best: Option<(i128, Vec<i64>)>,
+}
+impl Search<'_> {
+    fn branch(&mut self, index: usize, remaining: (i128, i128), cost: i128) {
+        if remaining.0 < 0 || remaining.1 < 0 {
+            return;
+        }
+        let rest = &self.buttons[index..];
+        if rest.len() <= 2 {
+            if let Some(last) = self.solve_last(rest, remaining) {
+                let total = cost
+                    + rest
+                        .iter()
+                        .zip(&last)
+                        .map(|(b, &n)| b.cost as i128 * n as i128)
+                        .sum::<i128>();
+                if self.best.as_ref().is_none_or(|(best, _)| total < *best) {
+                    self.presses[index..].copy_from_slice(&last);
+                    self.best = Some((total, self.presses.clone()));
+                }
+            }
+            return;
+        }
+        if !self.beatable(cost, lp_cost(rest, remaining)) {
+            return;
+        }
+        let (button, others) = (rest[0], &rest[1..]);
+        let Some((low, high)) = self.press_range(button, others, remaining) else {
+            return;
+        };
+        let Some((first, step)) = lattice_presses(button.increment, others, remaining) else {
+            return;
+        };
+        // The LP cost with n presses of this button, n having to be between low and high
+        let with_presses = |n: i128| {
+            let left = (
+                remaining.0 - n * button.increment.0 as i128,
+                remaining.1 - n * button.increment.1 as i128,
+            );
+            let (num, den) = lp_cost(others, left)?;
+            Some((num + n * button.cost as i128 * den, den))
+        };
+        // Lowest point of the LP cost, the first n where pressing once more stops helping
+        let (mut lo, mut hi) = (low, high);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match (with_presses(mid), with_presses(mid + 1)) {
+                (Some(here), Some(next)) if less(next, here) => lo = mid + 1,
+                _ => hi = mid,
+            }
+        }
+        let lowest = lo;
+
+        let try_presses = |search: &mut Self, n: i128| {
+            if !search.beatable(cost, with_presses(n)) {
+                return false;
+            }
+            search.presses[index] = n as i64;
+            search.branch(
+                index + 1,
+                (
+                    remaining.0 - n * button.increment.0 as i128,
+                    remaining.1 - n * button.increment.1 as i128,
+                ),
+                cost + n * button.cost as i128,
+            );
+            true
+        };
+        if step == 0 {
+            if (low..=high).contains(&first) {
+                try_presses(self, first);
+            }
+            return;
+        }
+        // The first reachable count at or above the lowest point, then outwards both ways
+        let above = lowest + (first - lowest).rem_euclid(step);
+        let mut n = above;
+        while n <= high && try_presses(self, n) {
+            n += step;
+        }
+        let mut n = above - step;
+        while n >= low && try_presses(self, n) {
+            n -= step;
+        }
+    }
+    // Whether a real valued cost (numerator, denominator) on top of cost so far could still win
+    fn beatable(&self, cost: i128, lp: Option<(i128, i128)>) -> bool {
+        let Some((num, den)) = lp else {
+            return false;
+        };
+        // Whole costs have to come in at least 1 under the best
+        self.best
+            .as_ref()
+            .is_none_or(|(best, _)| (cost - best + 1) * den + num <= 0)
+    }
+    // Fewest and most presses of the button that leave the rest of the prize inside what the other
+    // buttons can point at, and within the limit
+    fn press_range(
+        &self,
+        button: Button,
+        others: &[Button],
+        remaining: (i128, i128),
+    ) -> Option<(i128, i128)> {
+        let (dx, dy) = (button.increment.0 as i128, button.increment.1 as i128);
+        if (dx, dy) == (0, 0) {
+            // Doesn't move the claw, so it's never worth paying for
+            return Some((0, 0));
+        }
+        let mut range = KRange::default();
+        range.at_least_zero(0, 1);
+        if let Some(limit) = self.press_limit {
+            range.at_least_zero(limit as i128, -1);
+        }
+        // What's left after n presses is remaining - n * (dx, dy), and has to stay on the board
+        range.at_least_zero(remaining.0, -dx);
+        range.at_least_zero(remaining.1, -dy);
+        let moving = others
+            .iter()
+            .map(|b| (b.increment.0 as i128, b.increment.1 as i128))
+            .filter(|&d| d != (0, 0));
+        // Cross product of a with what's left, as constant + coefficient * n
+        let cross = |a: (i128, i128)| (a.0 * remaining.1 - a.1 * remaining.0, a.1 * dx - a.0 * dy);
+        let flattest = moving
+            .clone()
+            .min_by(|&a, &b| 0.cmp(&(a.0 * b.1 - a.1 * b.0)));
+        let steepest = moving.max_by(|&a, &b| 0.cmp(&(a.0 * b.1 - a.1 * b.0)));
+        match (flattest, steepest) {
+            (Some(flattest), Some(steepest)) => {
+                // Between the flattest and steepest of the other buttons
+                let (constant, coefficient) = cross(flattest);
+                range.at_least_zero(constant, coefficient);
+                let (constant, coefficient) = cross(steepest);
+                range.at_least_zero(-constant, -coefficient);
+            }
+            // Nothing else moves, so this button has to finish the job alone
+            _ => {
+                range.at_least_zero(-remaining.0, dx);
+                range.at_least_zero(-remaining.1, dy);
+            }
+        }
+        match (range.low, range.high) {
+            _ if range.empty => None,
+            (Some(low), Some(high)) if low <= high => Some((low, high)),
+            _ => None,
+        }
+    }
+    fn solve_last(&self, rest: &[Button], remaining: (i128, i128)) -> Option<Vec<i64>> {
+        let remaining = (
+            i64::try_from(remaining.0).ok()?,
+            i64::try_from(remaining.1).ok()?,
+        );
+        match rest {
+            [] => (remaining == (0, 0)).then(Vec::new),
+            [only] => {
+                let n = presses_along(only.increment, remaining)?;
+                let n = i64::try_from(n.0 / n.1).ok().filter(|_| n.0 % n.1 == 0)?;
+                (self.press_limit.is_none_or(|l| n <= l)).then(|| vec![n])
+            }
+            [a, b] => {
+                let (na, nb) = solve_two_buttons(
+                    a.increment,
+                    b.increment,
+                    remaining,
+                    (a.cost, b.cost),
+                    self.press_limit,
+                )?;
+                Some(vec![na, nb])
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+// Presses of one button to exactly reach the target as a fraction (numerator, denominator > 0)
+fn presses_along(increment: (i64, i64), target: (i64, i64)) -> Option<(i128, i128)> {
+    let (dx, dy) = (increment.0 as i128, increment.1 as i128);
+    let (tx, ty) = (target.0 as i128, target.1 as i128);
+    if (dx, dy) == (0, 0) {
+        return ((tx, ty) == (0, 0)).then_some((0, 1));
+    }
+    // Has to point the same way as the target
+    if dx * ty != dy * tx {
+        return None;
+    }
+    let presses = (tx * dx + ty * dy, dx * dx + dy * dy);
+    (presses.0 >= 0).then_some(presses)
+}
+
+// The cheapest real valued presses of the buttons left as (numerator, denominator > 0), None if even
+// that can't reach
+fn lp_cost(buttons: &[Button], remaining: (i128, i128)) -> Option<(i128, i128)> {
+    if remaining == (0, 0) {
+        return Some((0, 1));
+    }
+    let (tx, ty) = remaining;
+    let mut best: Option<(i128, i128)> = None;
+    let mut consider = |cost: (i128, i128)| {
+        if best.is_none_or(|b| less(cost, b)) {
+            best = Some(cost);
+        }
+    };
+    let target = (i64::try_from(tx).ok()?, i64::try_from(ty).ok()?);
+    for (i, a) in buttons.iter().enumerate() {
+        if let Some((num, den)) = presses_along(a.increment, target) {
+            consider((a.cost as i128 * num, den));
+        }
+        for b in &buttons[i + 1..] {
+            let (ax, ay) = (a.increment.0 as i128, a.increment.1 as i128);
+            let (bx, by) = (b.increment.0 as i128, b.increment.1 as i128);
+            let mut det = ax * by - ay * bx;
+            if det == 0 {
+                continue;
+            }
+            let (mut na, mut nb) = (tx * by - ty * bx, ax * ty - ay * tx);
+            if det < 0 {
+                (det, na, nb) = (-det, -na, -nb);
+            }
+            if na >= 0 && nb >= 0 {
+                consider((a.cost as i128 * na + b.cost as i128 * nb, det));
+            }
+        }
+    }
+    best
+}
+// The LP cost rounded up, as no whole answer can do better
+#[cfg(test)]
+fn lp_bound(buttons: &[Button], remaining: (i128, i128)) -> Option<i128> {
+    let (num, den) = lp_cost(buttons, remaining)?;
+    Some(-(-num).div_euclid(den))
+}
+// Compares fractions with positive denominators
+fn less(a: (i128, i128), b: (i128, i128)) -> bool {
+    a.0 * b.1 < b.0 * a.1
+}
+
+// The press counts n of a button that leave remaining - n * increment reachable in whole presses of
+// the others, ignoring that they can't go negative. They're every step-th count from first, or just
+// first when step is 0
+//
+// Whole combinations of the others form a lattice, which gets built up one button at a time as
+// multiples of (x, y) plus multiples of (0, c), x being the gcd of all their X increments. Landing on
+// it is then two congruences, X first and then Y along the counts that pass X
+fn lattice_presses(
+    increment: (i64, i64),
+    others: &[Button],
+    remaining: (i128, i128),
+) -> Option<(i128, i128)> {
+    let (mut x, mut y, mut c) = (0, 0, 0);
+    for other in others {
+        let (ox, oy) = (other.increment.0 as i128, other.increment.1 as i128);
+        if ox == 0 {
+            c = extended_gcd(c, oy).0;
+        } else if x == 0 {
+            (x, y) = (ox, oy);
+        } else {
+            // Swaps (x, y) and (ox, oy) for the same lattice with one of them on the Y axis
+            let (g, p, q) = extended_gcd(x, ox);
+            let on_axis = (ox / g) * y - (x / g) * oy;
+            (x, y) = (g, p * y + q * oy);
+            c = extended_gcd(c, on_axis).0;
+        }
+    }
+    if x < 0 {
+        (x, y) = (-x, -y);
+    }
+    let (dx, dy) = (increment.0 as i128, increment.1 as i128);
+    let (rx, ry) = remaining;
+    // X: n * dx = rx, modulo x
+    let (n0, n_step) = congruence(dx, rx, x)?;
+    // How many of (x, y) that takes, as k0 - t * k_step for the t-th count along
+    let (k0, k_step) = if x == 0 {
+        (0, 0)
+    } else {
+        ((rx - n0 * dx) / x, n_step * dx / x)
+    };
+    // Y: what's left over has to be a multiple of c
+    let left_over = ry - n0 * dy - k0 * y;
+    let per_step = n_step * dy - k_step * y;
+    let (t0, t_step) = congruence(per_step, left_over, c)?;
+    Some((n0 + n_step * t0, n_step * t_step))
+}
+// Every n with a * n = b modulo m (exactly when m is 0), as every step-th from start, step 0 being
+// just start
+fn congruence(a: i128, b: i128, m: i128) -> Option<(i128, i128)> {
+    if m == 0 {
+        return match a {
+            0 => (b == 0).then_some((0, 1)),
+            _ => (b % a == 0).then_some((b / a, 0)),
+        };
+    }
+    let (g, inverse, _) = extended_gcd(a, m);
+    if b % g != 0 {
+        return None;
+    }
+    let step = m / g;
+    Some(((inverse * (b / g)).rem_euclid(step), step))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buttons(spec: &[((i64, i64), i64)]) -> Vec<Button> {
+        spec.iter()
+            .zip('A'..)
+            .map(|(&(increment, cost), label)| Button {
+                label,
+                increment,
+                cost,
+            })
+            .collect()
+    }
+    fn cost(buttons: &[Button], presses: &[i64]) -> i64 {
+        buttons.iter().zip(presses).map(|(b, n)| b.cost * n).sum()
+    }
+    // Every combination of presses up to the limit
+    fn brute_force(buttons: &[Button], target: (i64, i64), limit: i64) -> Option<i64> {
+        let mut best = None;
+        let mut presses = vec![0; buttons.len()];
+        loop {
+            let reached = buttons.iter().zip(&presses).fold((0, 0), |(x, y), (b, n)| {
+                (x + b.increment.0 * n, y + b.increment.1 * n)
+            });
+            if reached == target {
+                let cost = cost(buttons, &presses);
+                best = Some(best.map_or(cost, |b: i64| b.min(cost)));
+            }
+            let Some(i) = presses.iter().position(|&n| n < limit) else {
+                return best;
+            };
+            presses[..i].iter_mut().for_each(|n| *n = 0);
+            presses[i] += 1;
+        }
+    }
+
+    #[test]
+    fn test_three_buttons() {
+        // C takes the claw diagonally for less than A and B together
+        let machine = buttons(&[((1, 0), 3), ((0, 1), 3), ((1, 1), 4)]);
+        assert_eq!(
+            cheapest_presses(&machine, (5, 3), None),
+            Some(vec![2, 0, 3])
+        );
+        assert_eq!(cheapest_presses(&machine, (5, 3), Some(2)), None);
+        let machine = buttons(&[((1, 0), 3), ((0, 1), 3), ((1, 1), 7)]);
+        assert_eq!(
+            cheapest_presses(&machine, (5, 3), None),
+            Some(vec![5, 3, 0])
+        );
+    }
+    #[test]
+    fn test_big_targets() {
+        // Part B scale, A's presses alone run to about 10^11
+        let machine = buttons(&[((94, 34), 3), ((22, 67), 1), ((17, 19), 2)]);
+        let target = (10_000_000_008_400, 10_000_000_005_400);
+        let presses = cheapest_presses(&machine, target, None).unwrap();
+        assert_eq!(presses, [81_081_080_557, 108_108_107_136, 4_650]);
+        assert_eq!(cost(&machine, &presses), 351_351_358_107);
+        // C can't help when it costs more than going the long way with A and B
+        let machine = buttons(&[((26, 66), 3), ((67, 21), 1), ((17, 19), 100)]);
+        let target = (10_000_000_012_748, 10_000_000_012_176);
+        assert_eq!(
+            cheapest_presses(&machine, target, None),
+            Some(vec![118679050709, 103199174542, 0])
+        );
+    }
+    #[test]
+    fn test_lattice_presses() {
+        // B and C only reach even X, so A has to be pressed an odd number of times to reach X = 5
+        let others = buttons(&[((2, 0), 1), ((0, 1), 1)]);
+        assert_eq!(lattice_presses((1, 0), &others, (5, 3)), Some((1, 2)));
+        assert_eq!(lattice_presses((2, 0), &others, (5, 3)), None);
+        // Nothing else moves, so A has to do it all
+        assert_eq!(lattice_presses((2, 1), &[], (6, 3)), Some((3, 0)));
+        assert_eq!(lattice_presses((0, 0), &[], (0, 0)), Some((0, 1)));
+    }
+    #[test]
+    fn test_lp_bound() {
+        let machine = buttons(&[((2, 0), 1), ((0, 3), 1), ((1, 1), 5)]);
+        // Half a press of A and a third of B, rounded up
+        assert_eq!(lp_bound(&machine, (1, 1)), Some(1));
+        assert_eq!(lp_bound(&machine[..2], (4, 0)), Some(2));
+        assert_eq!(lp_bound(&machine[..1], (4, 1)), None);
+    }
+    #[test]
+    fn test_matches_brute_force() {
+        let machines = [
+            buttons(&[((1, 2), 3), ((2, 1), 1), ((3, 3), 2)]),
+            buttons(&[((1, 0), 1), ((0, 1), 1), ((2, 2), 3), ((1, 3), 2)]),
+            buttons(&[((2, 4), 1), ((1, 2), 1), ((3, 6), 2)]),
+            buttons(&[((0, 0), 1), ((3, 1), 2), ((1, 3), 2), ((4, 4), 3)]),
+            buttons(&[((5, 2), 4)]),
+            buttons(&[]),
+        ];
+        for machine in &machines {
+            for target in [(0, 0), (6, 6), (7, 5), (10, 4), (8, 16), (4, 12), (9, 3)] {
+                let found = cheapest_presses(machine, target, Some(6));
+                let expected = brute_force(machine, target, 6);
+                assert_eq!(
+                    found.as_deref().map(|p| cost(machine, p)),
+                    expected,
+                    "{target:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use rayon::prelude::*;
+use regex::Regex;
+use shared::{numbered_lines, Answer, ParseError, Solution};
+
+mod exact;
+mod ilp;
+pub use exact::*;
+pub use ilp::*;
+
+shared::register_day!(13, Day13);
+
+pub struct Day13;
+impl Solution for Day13 {
+    type Input = Vec<Machine>;
+    type Config = Day13Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        machines_from_input(input)
+    }
+    fn part_a(input: &Self::Input, config: &Day13Config) -> Answer {
+        part_a(input, config).into()
+    }
+    fn part_b(input: &Self::Input, config: &Day13Config) -> Answer {
+        part_b(input, config).into()
+    }
+}
+pub struct Day13Config {
+    pub part_a_press_limit: Option<i64>, // No button gets pressed more than this in part A
+    pub part_b_offset: i64,              // Added to both prize coordinates in part B
+}
+impl Default for Day13Config {
+    fn default() -> Self {
+        Self {
+            part_a_press_limit: Some(100),
+            part_b_offset: 10000000000000,
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Button {
+    pub label: char,
+    pub increment: (i64, i64),
+    pub cost: i64, // Tokens per press
+}
+// What the puzzle charges, when the input doesn't say
+fn default_cost(label: char) -> i64 {
+    if label == 'A' {
+        3
+    } else {
+        1
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Machine {
+    buttons: Vec<Button>,
+    target_location: (i64, i64),
+}
+impl Machine {
+    pub fn buttons(&self) -> &[Button] {
+        &self.buttons
+    }
+    pub fn target_location(&self) -> (i64, i64) {
+        self.target_location
+    }
+    // Cheapest presses of each button that land on the prize
+    // Two buttons are solved exactly, see `solve_two_buttons`, any more go through `cheapest_presses`
+    pub fn find_presses(&self, press_limit: Option<i64>) -> Option<Vec<i64>> {
+        match &self.buttons[..] {
+            [a, b] => {
+                let (a_count, b_count) = solve_two_buttons(
+                    a.increment,
+                    b.increment,
+                    self.target_location,
+                    (a.cost, b.cost),
+                    press_limit,
+                )?;
+                Some(vec![a_count, b_count])
+            }
+            buttons => cheapest_presses(buttons, self.target_location, press_limit),
+        }
+    }
+    pub fn find_cost(&self, press_limit: Option<i64>) -> Option<i64> {
+        let presses = self.find_presses(press_limit)?;
+        Some(
+            self.buttons
+                .iter()
+                .zip(presses)
+                .map(|(button, count)| button.cost * count)
+                .sum(),
+        )
+    }
+}
+// Machines are blank line separated, with buttons in order from A and the prize last
+//
+//     Button A: X+94, Y+34
+//     Button B: X+22, Y+67
+//     Button C: X+5, Y+5, Cost=2
+//     Prize: X=8400, Y=5400
+//
+// Costs are optional, A defaults to 3 tokens and the rest to 1
+fn machines_from_input(input: &str) -> Result<Vec<Machine>, ParseError> {
+    let mut machines = Vec::with_capacity(100);
+    let mut current_machine = Machine::default();
+    let mut has_prize = false;
+    let regex_button =
+        Regex::new(r"^Button ([A-Z]): X\+(\d+), Y\+(\d+)(?:, Cost=(\d+))?$").unwrap();
+    let regex_prize = Regex::new(r"^Prize: X=(\d+), Y=(\d+)$").unwrap();
+    let last_line = input.lines().count() + 1;
+    let lines = numbered_lines(input).chain([(last_line, "")]);
+    for (line_number, line) in lines {
+        if line.is_empty() {
+            //End of machine
+            if !current_machine.buttons.is_empty() || has_prize {
+                if !has_prize {
+                    return Err(ParseError::new("Machine has no prize").with_line(line_number));
+                }
+                machines.push(std::mem::take(&mut current_machine));
+                has_prize = false;
+            }
+        } else if has_prize {
+            return Err(ParseError::new(format!(
+                "Expected a blank line after the prize, found `{line}`"
+            ))
+            .with_line(line_number));
+        } else if let Some(cap) = regex_button.captures(line) {
+            let label = cap[1].chars().next().unwrap();
+            let expected = (b'A' + current_machine.buttons.len() as u8) as char;
+            if label != expected {
+                return Err(ParseError::new(format!(
+                    "Expected Button {expected}, found Button {label}"
+                ))
+                .with_line(line_number));
+            }
+            let increment = (cap[2].parse::<i64>()?, cap[3].parse::<i64>()?);
+            let cost = match cap.get(4) {
+                Some(cost) => cost.as_str().parse()?,
+                None => default_cost(label),
+            };
+            current_machine.buttons.push(Button {
+                label,
+                increment,
+                cost,
+            });
+        } else if let Some(cap) = regex_prize.captures(line) {
+            current_machine.target_location = (cap[1].parse::<i64>()?, cap[2].parse::<i64>()?);
+            has_prize = true;
+        } else {
+            return Err(ParseError::new(format!("Unknown line: {line}")).with_line(line_number));
+        }
+    }
+    Ok(machines)
+}
+fn part_a(machines: &[Machine], config: &Day13Config) -> i64 {
+    // println!("Machines {:?}", machines);
+
+    machines
+        .par_iter()
+        .map(|machine| {
+            let cost = machine.find_cost(config.part_a_press_limit);
+            // println!("Cost: {:?} -> {:?}", machine, cost);
+            cost.unwrap_or_default()
+        })
+        .sum()
+}
+fn part_b(machines: &[Machine], config: &Day13Config) -> i64 {
+    let mut machines = machines.to_vec();
+    // println!("Machines {:?}", machines);
+    //Update machine targers
+    machines.iter_mut().for_each(|machine| {
+        machine.target_location.0 += config.part_b_offset;
+        machine.target_location.1 += config.part_b_offset;
+    });
+
+    machines
+        .into_iter()
+        .map(|machine| machine.find_cost(None).unwrap_or_default())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day13::solve_file("test.txt", Part::A);
+        assert_eq!(results, 480);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day13::solve_file("input.txt", Part::A);
+        assert_eq!(results, 28262);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day13::solve_file("test.txt", Part::B);
+        assert_eq!(results, 875318608908);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day13::solve_file("input.txt", Part::B);
+        assert_eq!(results, 101406661266314);
+    }
+    fn machine(buttons: &[(i64, i64)], target_location: (i64, i64)) -> Machine {
+        Machine {
+            buttons: buttons
+                .iter()
+                .zip('A'..)
+                .map(|(&increment, label)| Button {
+                    label,
+                    increment,
+                    cost: default_cost(label),
+                })
+                .collect(),
+            target_location,
+        }
+    }
+    #[test]
+    fn test_press_limit() {
+        let machines = Day13::parse(&std::fs::read_to_string("test.txt").unwrap()).unwrap();
+        assert_eq!(machines[0].find_presses(Some(100)), Some(vec![80, 40]));
+        assert_eq!(machines[0].find_cost(Some(79)), None);
+        // Too many presses for part A, but fine without the limit
+        let far = Machine {
+            target_location: (94 * 150 + 22 * 10, 34 * 150 + 67 * 10),
+            ..machines[0].clone()
+        };
+        assert_eq!(far.find_cost(Some(100)), None);
+        assert_eq!(far.find_cost(None), Some(460));
+    }
+    #[test]
+    fn test_degenerate_machines() {
+        // Both buttons push the same way, B does it cheaper per step
+        assert_eq!(
+            machine(&[(1, 1), (3, 3)], (10, 10)).find_presses(None),
+            Some(vec![1, 3])
+        );
+        // Two identical buttons, so only the cheaper one gets pressed
+        assert_eq!(
+            machine(&[(2, 5), (2, 5)], (8, 20)).find_presses(None),
+            Some(vec![0, 4])
+        );
+        assert_eq!(machine(&[(2, 5), (2, 5)], (8, 21)).find_cost(None), None);
+        // A button that does nothing
+        assert_eq!(machine(&[(0, 0), (7, 2)], (14, 4)).find_cost(None), Some(2));
+        assert_eq!(machine(&[(0, 0), (0, 0)], (0, 0)).find_cost(None), Some(0));
+        // Fewer buttons than usual
+        assert_eq!(machine(&[(3, 4)], (9, 12)).find_cost(None), Some(9));
+        assert_eq!(machine(&[], (0, 0)).find_cost(None), Some(0));
+    }
+    #[test]
+    fn test_more_buttons() {
+        let input = "Button A: X+1, Y+0
+Button B: X+0, Y+1
+Button C: X+1, Y+1, Cost=1
+Prize: X=5, Y=3
+
+Button A: X+94, Y+34, Cost=1
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400";
+        let machines = Day13::parse(input).unwrap();
+        assert_eq!(machines[0].buttons()[2].cost, 1);
+        assert_eq!(machines[0].find_presses(None), Some(vec![2, 0, 3]));
+        assert_eq!(machines[0].find_cost(None), Some(9));
+        assert_eq!(machines[1].find_cost(Some(100)), Some(120));
+        // Checked against each other on the demo with a do-nothing button added
+        for mut machine in Day13::parse(&std::fs::read_to_string("test.txt").unwrap()).unwrap() {
+            let two = machine.find_cost(Some(100));
+            machine.buttons.insert(
+                0,
+                Button {
+                    label: 'Z',
+                    increment: (0, 0),
+                    cost: 1,
+                },
+            );
+            assert_eq!(machine.find_cost(Some(100)), two);
+        }
+    }
+    #[test]
+    fn test_parse_errors() {
+        let error = |input: &str| Day13::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("Button A: X+1, Y+2\nButton C: X+1, Y+2\nPrize: X=1, Y=1"),
+            "2: Expected Button B, found Button C"
+        );
+        assert_eq!(error("Button A: X+1, Y+2\n\n"), "2: Machine has no prize");
+        assert_eq!(
+            error("Prize: X=1, Y=1\nButton A: X+1, Y+2"),
+            "2: Expected a blank line after the prize, found `Button A: X+1, Y+2`"
+        );
+        assert_eq!(
+            error("Button A: X-1, Y+2"),
+            "1: Unknown line: Button A: X-1, Y+2"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use rayon::prelude::*;
+use regex::Regex;
+use shared::{
+    viz::{Palette, Recorder},
+    Answer, Grid, ParseError, Part, Position, Solution,
+};
+
+shared::register_day!(14, Day14);
+
+pub struct Day14;
+impl Solution for Day14 {
+    type Input = Vec<Robot>;
+    type Config = Day14Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(Robot::from_line)
+            .collect()
+    }
+    fn part_a(input: &Self::Input, config: &Day14Config) -> Answer {
+        part_a(input, config).into()
+    }
+    fn part_b(input: &Self::Input, config: &Day14Config) -> Answer {
+        part_b(input, config).into()
+    }
+    fn visualize(input: &Self::Input, _part: Part, config: &Day14Config) -> Option<Recorder> {
+        Some(record_robots(input, config))
+    }
+}
+pub struct Day14Config {
+    pub grid_rows: i64,
+    pub grid_cols: i64,
+    pub ticks: i64, // Seconds the robots move for in part A
+}
+impl Default for Day14Config {
+    fn default() -> Self {
+        Self {
+            grid_rows: 103,
+            grid_cols: 101,
+            ticks: 100,
+        }
+    }
+}
+#[derive(Debug, Clone)]
+pub struct Robot {
+    position: (i64, i64), // Row-Column, not like questions broken column-row :wink:
+    movement_velocity: (i64, i64), //Cells per tick, as above
+}
+impl Robot {
+    fn from_line(line: &str) -> Result<Self, ParseError> {
+        // p=9,3 v=2,3
+        let regex_pos: Regex = Regex::new(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)$").unwrap();
+        let cap = regex_pos
+            .captures(line)
+            .ok_or_else(|| ParseError::new(format!("Invalid robot `{line}`")))?;
+        let col = cap[1].parse::<i64>()?;
+        let row = cap[2].parse::<i64>()?;
+        let horizontal = cap[3].parse::<i64>()?;
+        let vertical = cap[4].parse::<i64>()?;
+        Ok(Self {
+            position: (row, col),
+            movement_velocity: (vertical, horizontal),
+        })
+    }
+    // Where the robot is after `tick` seconds, wrapping round the edges
+    // Only the tick modulo the grid size matters, so huge tick counts can't overflow
+    pub fn position_at(&self, tick: i64, grid_rows: i64, grid_cols: i64) -> (i64, i64) {
+        let rows = tick.rem_euclid(grid_rows);
+        let cols = tick.rem_euclid(grid_cols);
+        (
+            (self.position.0 + self.movement_velocity.0 * rows).rem_euclid(grid_rows),
+            (self.position.1 + self.movement_velocity.1 * cols).rem_euclid(grid_cols),
+        )
+    }
+}
+
+fn robot_positions(robots: &[Robot], tick: i64, config: &Day14Config) -> Vec<(i64, i64)> {
+    robots
+        .iter()
+        .map(|robot| robot.position_at(tick, config.grid_rows, config.grid_cols))
+        .collect()
+}
+fn part_a(robots: &[Robot], config: &Day14Config) -> i64 {
+    let positions = robot_positions(robots, config.ticks, config);
+    // println!("Robots {positions:?}");
+    let quad_counts = quadrant_counts(&positions, config.grid_rows, config.grid_cols);
+    quad_counts[0] * quad_counts[1] * quad_counts[2] * quad_counts[3]
+}
+// Robots in each quadrant, those on the middle row or column are in none
+fn quadrant_counts(positions: &[(i64, i64)], grid_rows: i64, grid_cols: i64) -> [i64; 4] {
+    let middle_row = grid_rows / 2;
+    let middle_col = grid_cols / 2;
+    // println!("middle {middle_row} {middle_col}");
+    let mut quad_counts = [0, 0, 0, 0];
+    for &(row, col) in positions {
+        if row < middle_row && col < middle_col {
+            quad_counts[0] += 1;
+        } else if row < middle_row && col > middle_col {
+            quad_counts[1] += 1;
+        } else if row > middle_row && col < middle_col {
+            quad_counts[2] += 1;
+        } else if row > middle_row && col > middle_col {
+            quad_counts[3] += 1;
+        }
+    }
+    quad_counts
+}
+
+// How picture-like the robots look after a tick
+#[derive(Debug, Clone, PartialEq)]
+pub struct TickScore {
+    pub tick: usize,
+    pub entropy: f64, // Bits across the row and column histograms, low when robots bunch up
+    pub quadrant_variance: f64, // High when one quadrant holds most of the robots
+    pub longest_run: usize, // Most robots side by side on one row
+    pub components: usize, // Groups of touching robots, low when they form a shape
+}
+impl TickScore {
+    fn new(tick: usize, positions: &[(i64, i64)], grid_rows: i64, grid_cols: i64) -> Self {
+        Self {
+            tick,
+            entropy: histogram_entropy(positions.iter().map(|p| p.0), grid_rows)
+                + histogram_entropy(positions.iter().map(|p| p.1), grid_cols),
+            quadrant_variance: variance(
+                quadrant_counts(positions, grid_rows, grid_cols).map(|count| count as f64),
+            ),
+            longest_run: longest_horizontal_run(positions),
+            components: count_components(positions, grid_rows, grid_cols),
+        }
+    }
+    // Each heuristic turned around so bigger means more like a picture
+    fn features(&self) -> [f64; 4] {
+        [
+            -self.entropy,
+            self.quadrant_variance,
+            self.longest_run as f64,
+            -(self.components as f64),
+        ]
+    }
+}
+fn histogram_entropy(values: impl Iterator<Item = i64>, buckets: i64) -> f64 {
+    let mut counts = vec![0usize; buckets as usize];
+    let mut total = 0;
+    for value in values {
+        counts[value as usize] += 1;
+        total += 1;
+    }
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+fn variance<const N: usize>(values: [f64; N]) -> f64 {
+    let mean = values.iter().sum::<f64>() / N as f64;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / N as f64
+}
+fn longest_horizontal_run(positions: &[(i64, i64)]) -> usize {
+    let mut sorted = positions.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let mut longest = 0;
+    let mut run = 0;
+    for (i, &(row, col)) in sorted.iter().enumerate() {
+        if i > 0 && sorted[i - 1] == (row, col - 1) {
+            run += 1;
+        } else {
+            run = 1;
+        }
+        longest = longest.max(run);
+    }
+    longest
+}
+// Groups of occupied cells touching up, down, left or right
+fn count_components(positions: &[(i64, i64)], grid_rows: i64, grid_cols: i64) -> usize {
+    let mut occupied = Grid::filled_with(false, grid_rows as usize, grid_cols as usize);
+    for &(row, col) in positions {
+        occupied[(row as usize, col as usize)] = true;
+    }
+    let mut components = 0;
+    for &(row, col) in positions {
+        let start = (row as usize, col as usize);
+        if !occupied[start] {
+            continue;
+        }
+        components += 1;
+        occupied[start] = false;
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            let next: Vec<Position> = occupied.neighbours4(pos).filter(|&n| occupied[n]).collect();
+            for n in next {
+                occupied[n] = false;
+                stack.push(n);
+            }
+        }
+    }
+    components
+}
+
+// Score every tick until the robots are back where they started
+// Each robot repeats every `grid_rows` ticks vertically and `grid_cols` horizontally, so by CRT
+// the whole picture repeats within grid_rows * grid_cols ticks
+pub fn score_ticks(robots: &[Robot], config: &Day14Config) -> Vec<TickScore> {
+    let period = (config.grid_rows * config.grid_cols) as usize;
+    (0..period)
+        .into_par_iter()
+        .map(|tick| {
+            let positions = robot_positions(robots, tick as i64, config);
+            TickScore::new(tick, &positions, config.grid_rows, config.grid_cols)
+        })
+        .collect()
+}
+// The tick that stands out furthest from the rest, adding up how many standard deviations
+// each heuristic is from its average
+pub fn most_likely_tick(scores: &[TickScore]) -> Option<&TickScore> {
+    let features: Vec<[f64; 4]> = scores.iter().map(TickScore::features).collect();
+    let count = features.len() as f64;
+    let mut means = [0.0; 4];
+    let mut deviations = [0.0; 4];
+    for i in 0..4 {
+        means[i] = features.iter().map(|f| f[i]).sum::<f64>() / count;
+        let variance = features
+            .iter()
+            .map(|f| (f[i] - means[i]).powi(2))
+            .sum::<f64>()
+            / count;
+        // A heuristic that never changes can't pick anything out
+        deviations[i] = if variance > 0.0 {
+            variance.sqrt()
+        } else {
+            f64::INFINITY
+        };
+    }
+    let anomaly = |f: &[f64; 4]| -> f64 { (0..4).map(|i| (f[i] - means[i]) / deviations[i]).sum() };
+    scores
+        .iter()
+        .zip(&features)
+        .max_by(|(_, a), (_, b)| anomaly(a).total_cmp(&anomaly(b)))
+        .map(|(score, _)| score)
+}
+fn part_b(robots: &[Robot], config: &Day14Config) -> i64 {
+    let scores = score_ticks(robots, config);
+    let best = most_likely_tick(&scores).unwrap();
+    best.tick as i64
+}
+// Every tick of the robots moving, up to where the tree appears
+fn record_robots(robots: &[Robot], config: &Day14Config) -> Recorder {
+    let total_ticks = part_b(robots, config);
+    let mut recorder = Recorder::new(Palette::monochrome()).scale(2);
+    for tick in 0..=total_ticks {
+        let positions: Vec<(usize, usize)> = robot_positions(robots, tick, config)
+            .into_iter()
+            .map(|(row, col)| (row as usize, col as usize))
+            .collect();
+        recorder.record_points(
+            config.grid_rows as usize,
+            config.grid_cols as usize,
+            &positions,
+            1,
+        );
+    }
+    recorder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEMO: Day14Config = Day14Config {
+        grid_rows: 7,
+        grid_cols: 11,
+        ticks: 100,
+    };
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day14::solve_file_with("test.txt", Part::A, &DEMO);
+        assert_eq!(results, 12);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day14::solve_file("input.txt", Part::A);
+        assert_eq!(results, 221655456);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day14::solve_file("input.txt", Part::B);
+        assert_eq!(results, 7858);
+    }
+    #[test]
+    fn test_position_at() {
+        // The example robot from the puzzle, on the demo grid
+        let robot = Robot::from_line("p=2,4 v=2,-3").unwrap();
+        assert_eq!(robot.position_at(0, 7, 11), (4, 2));
+        assert_eq!(robot.position_at(1, 7, 11), (1, 4));
+        assert_eq!(robot.position_at(5, 7, 11), (3, 1));
+        // Back to the start after a full period, however many there have been
+        assert_eq!(robot.position_at(77 * 1_000_000_000_000, 7, 11), (4, 2));
+    }
+    #[test]
+    fn test_tick_heuristics() {
+        // A short line and a lone robot on a 5x5 grid
+        let positions = [(1, 1), (1, 2), (1, 3), (3, 0)];
+        let score = TickScore::new(0, &positions, 5, 5);
+        assert_eq!(score.longest_run, 3);
+        assert_eq!(score.components, 2);
+        // Quadrants hold 1, 1, 1 and 0 robots, the middle column is in none
+        assert_eq!(quadrant_counts(&positions, 5, 5), [1, 1, 1, 0]);
+        assert!((score.quadrant_variance - 0.1875).abs() < 1e-9);
+    }
+}
@@ -1,44 +1,84 @@
-use core::str;
+use shared::{
+    parse_grid,
+    viz::{Palette, Recorder, Rgb},
+    Answer, Grid, ParseError, Part, Solution,
+};
+use std::fmt::Display;
 
-use array2d::Array2D;
+shared::register_day!(15, Day15);
 
-fn main() {
-    let t_a = std::thread::spawn(|| {
-        println!("PART A: {}", part_a("input.txt"));
-    });
-    let t_b = std::thread::spawn(|| {
-        println!("PART B: {}", part_b("input.txt"));
-    });
-    t_a.join().unwrap();
-    t_b.join().unwrap();
+pub struct Day15;
+impl Solution for Day15 {
+    type Input = Warehouse;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (map, instructions) = load_file(input, false)?;
+        let (wide_map, _) = load_file(input, true)?;
+        Ok(Warehouse {
+            map,
+            wide_map,
+            instructions,
+        })
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+    fn visualize(input: &Self::Input, part: Part, _config: &()) -> Option<Recorder> {
+        let mut map = match part {
+            Part::A => input.map.clone(),
+            Part::B => input.wide_map.clone(),
+        };
+        let mut recorder =
+            Recorder::new(Palette::new(&COLOURS)).every_for_total(input.instructions.len(), 1_000);
+        map.record(&mut recorder);
+        for &instruction in input.instructions.iter() {
+            map.apply_instruction(instruction);
+            map.record(&mut recorder);
+        }
+        Some(recorder)
+    }
 }
+// Floor, walls, boxes and the robot
+const COLOURS: [Rgb; 4] = [
+    [0x10, 0x10, 0x18],
+    [0x80, 0x80, 0x80],
+    [0xC0, 0x90, 0x40],
+    [0xFF, 0x40, 0x40],
+];
+pub struct Warehouse {
+    map: Map,
+    wide_map: Map, // Part B's map, with everything but the robot doubled in width
+    instructions: Vec<u8>,
+}
+#[derive(Clone)]
 struct Map {
-    map: Array2D<u8>,
+    map: Grid<u8>,
     robot_position: (usize, usize),
 }
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut map = self.map.clone();
+        map[self.robot_position] = b'@';
+        write!(f, "{map}")
+    }
+}
 impl Map {
-    pub fn print(&self) {
-        for y in 0..self.map.num_rows() {
-            for x in 0..self.map.num_columns() {
-                if (y, x) == self.robot_position {
-                    print!("@");
-                } else {
-                    match self.map.get(y, x) {
-                        Some(b'O') => print!("O"),
-                        Some(b'#') => print!("#"),
-                        Some(b'.') => print!("."),
-                        Some(b'[') => print!("["),
-                        Some(b']') => print!("]"),
-                        _ => panic!("Invalid character"),
-                    }
-                }
-            }
-            println!();
-        }
+    fn record(&self, recorder: &mut Recorder) {
+        let mut frame = self.map.map(|&cell| match cell {
+            b'#' => 1,
+            b'O' | b'[' | b']' => 2,
+            _ => 0,
+        });
+        frame[self.robot_position] = 3;
+        recorder.record_grid(&frame, |&colour| colour);
     }
     fn get_coordinate_sum(&self) -> i64 {
         self.map
-            .enumerate_row_major()
+            .enumerate()
             .map(|(pos, &value)| {
                 if value == b'O' || value == b'[' {
                     return (pos.0 * 100) as i64 + pos.1 as i64;
@@ -48,7 +88,7 @@ impl Map {
             .sum()
     }
     fn can_bump_along(&self, position: (usize, usize), direction: u8, has_recursed: bool) -> bool {
-        let item_at_position = *self.map.get(position.0, position.1).unwrap();
+        let item_at_position = self.map[position];
         if !has_recursed
             && (direction == b'^' || direction == b'v')
             && (item_at_position == b'[' || item_at_position == b']')
@@ -66,8 +106,7 @@ impl Map {
         let objects_in_way: Vec<&u8> = match direction {
             b'^' => self
                 .map
-                .column_iter(position.1)
-                .unwrap()
+                .column(position.1)
                 .take(position.0)
                 .collect::<Vec<&u8>>()
                 .into_iter()
@@ -75,25 +114,14 @@ impl Map {
                 .collect(),
             b'<' => self
                 .map
-                .row_iter(position.0)
-                .unwrap()
+                .row(position.0)
                 .take(position.1)
                 .collect::<Vec<&u8>>()
                 .into_iter()
                 .rev()
                 .collect(),
-            b'>' => self
-                .map
-                .row_iter(position.0)
-                .unwrap()
-                .skip(position.1 + 1)
-                .collect(),
-            b'v' => self
-                .map
-                .column_iter(position.1)
-                .unwrap()
-                .skip(position.0 + 1)
-                .collect(),
+            b'>' => self.map.row(position.0).skip(position.1 + 1).collect(),
+            b'v' => self.map.column(position.1).skip(position.0 + 1).collect(),
             _ => panic!("Invalid instruction"),
         };
         let mut can_move = objects_in_way.contains(&&b'.');
@@ -140,13 +168,13 @@ impl Map {
         box_sync: bool,
     ) {
         //Recursively bump the items along the direction until we hit a wall or an empty space
-        let current_item = *self.map.get(position.0, position.1).unwrap();
+        let current_item = self.map[position];
         let moving_vertically = direction == b'^' || direction == b'v';
-        println!(
-            "Recursively bumping {position:?} -> {} by {}",
-            str::from_utf8(&[current_item]).unwrap(),
-            str::from_utf8(&[direction]).unwrap()
-        );
+        // println!(
+        //     "Recursively bumping {position:?} -> {} by {}",
+        //     str::from_utf8(&[current_item]).unwrap(),
+        //     str::from_utf8(&[direction]).unwrap()
+        // );
         if current_item == b'.' {
             return; //Dont move spaces, we move stuff onto them
         }
@@ -157,10 +185,9 @@ impl Map {
             b'v' => (position.0 + 1, position.1),
             _ => panic!("Invalid instruction `{direction}`"),
         };
-        let mut new_item = *self.map.get(new_position.0, new_position.1).unwrap();
+        let mut new_item = self.map[new_position];
         if new_item == b'#' {
-            self.print();
-            panic!("Cant move walls {box_sync}");
+            panic!("Cant move walls {box_sync}\n{self}");
         }
         if new_item != b'.' {
             // The space is taken, we need to recursively move the item at the new position in the same direction
@@ -168,7 +195,7 @@ impl Map {
             //If we are moving up or down and the box is double wide `[]` we need to move the other half as well
 
             if moving_vertically && (new_item == b'[' || new_item == b']') {
-                println!("Vertical, double box, case");
+                // println!("Vertical, double box, case");
                 let other_half_position = match new_item {
                     b'[' => (new_position.0, new_position.1 + 1),
                     b']' => (new_position.0, new_position.1 - 1),
@@ -180,7 +207,7 @@ impl Map {
         }
         // Special case, if we are moving up or down and the box is double wide `[]` we need to move the other half as well
         if moving_vertically && (current_item == b'[' || current_item == b']') && !box_sync {
-            println!("Vertical, double box, case");
+            // println!("Vertical, double box, case");
             let other_half_position = match current_item {
                 b'[' => (position.0, position.1 + 1),
                 b']' => (position.0, position.1 - 1),
@@ -189,19 +216,16 @@ impl Map {
             self.recursively_bump_items_along(other_half_position, direction, true);
         }
         //Grab the new item, just to check its a '.'
-        new_item = *self.map.get(new_position.0, new_position.1).unwrap();
+        new_item = self.map[new_position];
         if new_item == b'#' {
             panic!("Cant move walls");
         }
         if new_item == b'.' {
             //Move the item to the new position
-            self.map
-                .set(new_position.0, new_position.1, current_item)
-                .unwrap();
-            self.map.set(position.0, position.1, new_item).unwrap();
+            self.map[new_position] = current_item;
+            self.map[position] = new_item;
         } else {
-            self.print();
-            panic!("How did we get here??");
+            panic!("How did we get here??\n{self}");
         }
     }
     fn apply_instruction(&mut self, instruction: u8) {
@@ -213,14 +237,14 @@ impl Map {
             _ => panic!("Invalid instruction `{instruction}`"),
         };
         //Check if the new position is valid, if its going to move it onto an object 'O', we bump objects if possible
-        let item = self.map.get(new_position.0, new_position.1).unwrap();
-        if *item == b'.' {
+        let item = self.map[new_position];
+        if item == b'.' {
             //Trivial case, it just moves onto new position
             self.robot_position = new_position;
             // println!("Empty,trivial op");
             return;
         }
-        if *item == b'#' {
+        if item == b'#' {
             //It hits a wall, do nothing
             // println!("Wall, no-op");
             return;
@@ -230,7 +254,7 @@ impl Map {
         // Need to check if we need to move the object
         // We can bump the objects if from the current robot position to the wall has at least 1 empty space, in the direction of travel
         let can_move = self.can_bump_along(new_position, instruction, false);
-        println!("Robot can move? {can_move}");
+        // println!("Robot can move? {can_move}");
         if !can_move {
             return;
         }
@@ -239,8 +263,8 @@ impl Map {
         self.robot_position = new_position;
     }
 }
-fn load_file(path: &str, doubler: bool) -> (Map, Vec<u8>) {
-    let mut file_contents = std::fs::read_to_string(path).unwrap();
+fn load_file(input: &str, doubler: bool) -> Result<(Map, Vec<u8>), ParseError> {
+    let mut file_contents = input.to_owned();
     if doubler {
         file_contents = file_contents
             .replace("#", "##")
@@ -251,49 +275,45 @@ fn load_file(path: &str, doubler: bool) -> (Map, Vec<u8>) {
     let lines: Vec<&str> = file_contents.lines().collect();
     // lines.iter().for_each(|l| println!("Line> {l}"));
 
-    let split_blank_line_inded = lines.iter().position(|&x| x.is_empty()).unwrap();
+    let split_blank_line_inded = lines
+        .iter()
+        .position(|&x| x.is_empty())
+        .ok_or_else(|| ParseError::new("Missing blank line between map and instructions"))?;
     let map_lines = &lines[..split_blank_line_inded];
     let instruction_data = &lines[split_blank_line_inded + 1..]
         .concat()
         .replace("\r", "")
         .replace("\n", "");
 
-    let mut map = Array2D::from_row_major(
-        map_lines.concat().as_bytes(),
-        map_lines.len(),
-        map_lines[0].len(),
-    )
-    .unwrap();
+    let mut map = parse_grid(&map_lines.join("\n"))?;
     let robot_position = map
-        .enumerate_row_major()
-        .filter_map(|(pos, &value)| if value == b'@' { Some(pos) } else { None })
-        .next()
-        .unwrap();
+        .find(&b'@')
+        .ok_or_else(|| ParseError::new("Missing robot `@` in map"))?;
     //Replace the robot position with a dot to clear it
-    map.set(robot_position.0, robot_position.1, b'.').unwrap();
-    (
+    map[robot_position] = b'.';
+    Ok((
         Map {
             map,
             robot_position,
         },
         instruction_data.trim().as_bytes().to_vec(),
-    )
+    ))
 }
-fn part_a(path: &str) -> i64 {
-    let (mut map, instructions) = load_file(path, false);
-    map.print();
-    for instruction in instructions {
+fn part_a(warehouse: &Warehouse) -> i64 {
+    let mut map = warehouse.map.clone();
+    // println!("{map}");
+    for &instruction in warehouse.instructions.iter() {
         map.apply_instruction(instruction);
-        // map.print();
+        // println!("{map}");
     }
     map.get_coordinate_sum()
 }
-fn part_b(path: &str) -> i64 {
-    let (mut map, instructions) = load_file(path, true);
-    map.print();
-    for instruction in instructions {
+fn part_b(warehouse: &Warehouse) -> i64 {
+    let mut map = warehouse.wide_map.clone();
+    // println!("{map}");
+    for &instruction in warehouse.instructions.iter() {
         map.apply_instruction(instruction);
-        // map.print();
+        // println!("{map}");
     }
     map.get_coordinate_sum()
 }
@@ -304,22 +324,22 @@ mod tests {
 
     #[test]
     fn test_part_a_demo() {
-        let results = part_a("test.txt");
+        let results = Day15::solve_file("test.txt", Part::A);
         assert_eq!(results, 10092);
     }
     #[test]
     fn test_part_a_real() {
-        let results = part_a("input.txt");
+        let results = Day15::solve_file("input.txt", Part::A);
         assert_eq!(results, 1499739);
     }
     #[test]
     fn test_part_b_demo() {
-        let results = part_b("test.txt");
+        let results = Day15::solve_file("test.txt", Part::B);
         assert_eq!(results, 9021);
     }
     #[test]
     fn test_part_b_real() {
-        let results = part_b("input.txt");
+        let results = Day15::solve_file("input.txt", Part::B);
         assert_eq!(results, 1522215);
     }
 }
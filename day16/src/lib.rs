@@ -0,0 +1,209 @@
+use shared::{
+    parse_grid,
+    pathfinding::{self, ShortestPaths},
+    viz::{Palette, Recorder, Rgb},
+    Answer, Direction, Grid, ParseError, Part, Solution, ALL_DIRECTIONS,
+};
+use std::collections::HashSet;
+
+shared::register_day!(16, Day16);
+
+pub struct Day16;
+impl Solution for Day16 {
+    type Input = Map;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::parse(input, 1, 1000)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+    fn visualize(input: &Self::Input, _part: Part, _config: &()) -> Option<Recorder> {
+        Some(record_search(input))
+    }
+}
+
+pub struct Map {
+    grid: Grid<u8>,
+    start: (usize, usize),
+    end: (usize, usize),
+    start_direction: Direction,
+    cost_forwards: i64,
+    cost_rotate: i64,
+}
+impl Map {
+    pub fn parse(input: &str, cost_forwards: i64, cost_rotate: i64) -> Result<Self, ParseError> {
+        let grid = parse_grid(input)?;
+        let start_position = grid
+            .find(&b'S')
+            .ok_or_else(|| ParseError::new("Missing start `S` in map"))?;
+        let end_position = grid
+            .find(&b'E')
+            .ok_or_else(|| ParseError::new("Missing end `E` in map"))?;
+        let start_direction = Direction::Right;
+        Ok(Self {
+            grid,
+            start: start_position,
+            end: end_position,
+            start_direction,
+            cost_forwards,
+            cost_rotate,
+        })
+    }
+    fn find_possible_positions_and_their_cost(
+        &self,
+        current_position: (usize, usize),
+        current_direction: Direction,
+    ) -> Vec<(usize, usize, Direction, i64)> {
+        let mut results = Vec::new();
+        let direction_options = [
+            current_direction,
+            current_direction.turn_right(),
+            current_direction.turn_left(),
+        ];
+
+        for direction in direction_options.iter() {
+            if let Some(new_position) = self.grid.offset(current_position, direction.delta()) {
+                let value_at_new_position = self.grid[new_position];
+                if value_at_new_position == b'#' {
+                    // Walls are NO-STEP
+                    continue;
+                }
+                let cost = if *direction == current_direction {
+                    self.cost_forwards
+                } else {
+                    self.cost_rotate + self.cost_forwards
+                };
+                assert!(cost > 0);
+
+                results.push((new_position.0, new_position.1, *direction, cost));
+            }
+        }
+        results
+    }
+    // The map with the visited tiles marked
+    #[allow(unused)]
+    pub fn with_visited(&self, visited: &HashSet<(usize, usize)>) -> Grid<u8> {
+        let mut grid = self.grid.clone();
+        for &pos in visited {
+            grid[pos] = b'*';
+        }
+        grid
+    }
+
+    fn successors(&self, &(position, direction): &State) -> Vec<(State, i64)> {
+        self.find_possible_positions_and_their_cost(position, direction)
+            .into_iter()
+            .map(|(r, c, direction, cost)| (((r, c), direction), cost))
+            .collect()
+    }
+    fn explore(&self) -> ShortestPaths<State> {
+        pathfinding::dijkstra((self.start, self.start_direction), |state| {
+            self.successors(state)
+        })
+    }
+    //Need to find all tiles covered by the lowest cost path's
+    fn tiles_on_best_paths(&self, paths: &ShortestPaths<State>) -> HashSet<(usize, usize)> {
+        let end_states = self.end_states();
+        let (_, best_cost) = paths.cheapest(&end_states).unwrap();
+        let best_end_states = end_states
+            .iter()
+            .filter(|state| paths.cost(state) == Some(best_cost));
+        paths
+            .states_on_optimal_paths(best_end_states)
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+    fn end_states(&self) -> [State; 4] {
+        ALL_DIRECTIONS.map(|d| (self.end, d))
+    }
+}
+// Where the reindeer is and which way it faces
+type State = ((usize, usize), Direction);
+
+fn part_a(map: &Map) -> i64 {
+    // Every tile costs at least a step forwards, so the manhattan distance never overestimates
+    let (cost, _) = pathfinding::astar(
+        (map.start, map.start_direction),
+        |state| map.successors(state),
+        |&((r, c), _)| (r.abs_diff(map.end.0) + c.abs_diff(map.end.1)) as i64 * map.cost_forwards,
+        |&(pos, _)| pos == map.end,
+    )
+    .unwrap();
+    cost
+}
+fn part_b(map: &Map) -> i64 {
+    // Part B requires calculating all the isochrone on the graph
+    let paths = map.explore();
+    let covered_tiles = map.tiles_on_best_paths(&paths);
+    // println!("{}", map.with_visited(&covered_tiles));
+    covered_tiles.len() as i64
+}
+// The search spreading out from the start by cost, finishing on every tile of the best paths
+fn record_search(map: &Map) -> Recorder {
+    let paths = map.explore();
+    let mut reached: Vec<(i64, (usize, usize))> = paths
+        .costs()
+        .iter()
+        .map(|(&(pos, _), &cost)| (cost, pos))
+        .collect();
+    reached.sort();
+
+    let frame_count = 300;
+    let mut recorder = Recorder::new(Palette::new(&COLOURS));
+    let mut frame = map
+        .grid
+        .map(|&cell| if cell == b'#' { WALL } else { FLOOR });
+    for chunk in reached.chunks(reached.len().div_ceil(frame_count).max(1)) {
+        for &(_, pos) in chunk {
+            frame[pos] = EXPLORED;
+        }
+        recorder.record_grid(&frame, |&colour| colour);
+    }
+    for pos in map.tiles_on_best_paths(&paths) {
+        frame[pos] = BEST_PATH;
+    }
+    recorder.record_grid(&frame, |&colour| colour);
+    recorder
+}
+const FLOOR: u8 = 0;
+const WALL: u8 = 1;
+const EXPLORED: u8 = 2;
+const BEST_PATH: u8 = 3;
+const COLOURS: [Rgb; 4] = [
+    [0x10, 0x10, 0x18],
+    [0x80, 0x80, 0x80],
+    [0x30, 0x60, 0xC0],
+    [0xFF, 0xD0, 0x40],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day16::solve_file("test.txt", Part::A);
+        assert_eq!(results, 7036);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day16::solve_file("input.txt", Part::A);
+        assert_eq!(results, 90440);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day16::solve_file("test.txt", Part::B);
+        assert_eq!(results, 45);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day16::solve_file("input.txt", Part::B);
+        assert_eq!(results, 479);
+    }
+}
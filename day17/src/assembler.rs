@@ -0,0 +1,285 @@
+// Builds programs from mnemonic source, the same syntax the disassembler prints
+//
+//     .a 2024          ; Starting value for a register, the rest start at 0
+//     loop: adv 3      ; Labels end in a colon
+//           out a      ; Combo operands are 0 to 3 or a register
+//           jnz loop   ; Jumps take a label or an address
+//           .raw 9 1   ; Program words as they are, for anything that isn't a valid instruction
+//
+// The listing's `12:` address prefixes are accepted too, as long as they match where they sit
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use shared::{numbered_lines, read_input_file, tokens_with_columns, ParseError};
+
+use crate::{Instruction, MiniPC, Register, RegisterFile};
+
+fn parse_instruction(mnemonic: &str) -> Option<Instruction> {
+    (0..8)
+        .filter_map(Instruction::from_opcode)
+        .find(|i| i.mnemonic().eq_ignore_ascii_case(mnemonic))
+}
+fn is_label(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// An instruction waiting for its labels to be known, or raw words
+enum Pending<'a> {
+    Instruction {
+        line: usize,
+        instruction: Instruction,
+        operand: Option<(usize, &'a str)>,
+    },
+    Words(Vec<u64>),
+}
+
+impl MiniPC {
+    pub fn assemble(source: &str) -> Result<Self, ParseError> {
+        let mut register_file = RegisterFile::default();
+        let mut labels: HashMap<&str, usize> = HashMap::new();
+        let mut pending = Vec::new();
+        let mut address = 0;
+
+        for (line_number, line) in numbered_lines(source) {
+            let code = line.split(';').next().unwrap_or_default();
+            let mut tokens = tokens_with_columns(code).peekable();
+            // Labels, and address prefixes from a listing
+            while let Some(&(column, token)) = tokens.peek() {
+                let Some(name) = token.strip_suffix(':') else {
+                    break;
+                };
+                tokens.next();
+                if let Ok(prefix) = name.parse::<usize>() {
+                    if prefix != address {
+                        return Err(ParseError::at(
+                            line_number,
+                            column,
+                            token,
+                            format!(
+                                "Address prefix doesn't match the instruction's address {address}"
+                            ),
+                        ));
+                    }
+                } else if !is_label(name) {
+                    return Err(ParseError::at(line_number, column, token, "Invalid label"));
+                } else if labels.insert(name, address).is_some() {
+                    return Err(ParseError::at(
+                        line_number,
+                        column,
+                        token,
+                        "Duplicate label",
+                    ));
+                }
+            }
+            let Some((column, mnemonic)) = tokens.next() else {
+                continue;
+            };
+            if mnemonic.eq_ignore_ascii_case(".raw") {
+                let words = tokens
+                    .by_ref()
+                    .map(|(column, word)| shared::parse_token(word, line_number, column))
+                    .collect::<Result<Vec<u64>, _>>()?;
+                if words.is_empty() {
+                    return Err(ParseError::at(
+                        line_number,
+                        column,
+                        mnemonic,
+                        "Missing words",
+                    ));
+                }
+                address += words.len();
+                pending.push(Pending::Words(words));
+            } else if let Some(name) = mnemonic.strip_prefix('.') {
+                let register: Register = name.parse().map_err(|_| {
+                    ParseError::at(line_number, column, mnemonic, "Unknown directive")
+                })?;
+                let (value_column, value) = tokens.next().ok_or_else(|| {
+                    ParseError::at(line_number, column, mnemonic, "Missing register value")
+                })?;
+                register_file.registers[register.index()] =
+                    shared::parse_token(value, line_number, value_column)?;
+            } else {
+                let instruction = parse_instruction(mnemonic).ok_or_else(|| {
+                    ParseError::at(line_number, column, mnemonic, "Unknown instruction")
+                })?;
+                address += 2;
+                pending.push(Pending::Instruction {
+                    line: line_number,
+                    instruction,
+                    operand: tokens.next(),
+                });
+            }
+            if let Some((column, extra)) = tokens.next() {
+                return Err(ParseError::at(
+                    line_number,
+                    column,
+                    extra,
+                    "Unexpected token",
+                ));
+            }
+        }
+
+        let mut instructions = Vec::with_capacity(address);
+        for item in pending {
+            let (line, instruction, operand) = match item {
+                Pending::Words(words) => {
+                    instructions.extend(words);
+                    continue;
+                }
+                Pending::Instruction {
+                    line,
+                    instruction,
+                    operand,
+                } => (line, instruction, operand),
+            };
+            let operand = match (instruction, operand) {
+                // Its operand is ignored, so it can be left off
+                (Instruction::BxorC, None) => 0,
+                (_, None) => {
+                    return Err(ParseError::new(format!(
+                        "Missing operand for `{}`",
+                        instruction.mnemonic()
+                    ))
+                    .with_line(line))
+                }
+                (_, Some((column, token))) => resolve_operand(instruction, token, &labels)
+                    .map_err(|message| ParseError::at(line, column, token, message))?,
+            };
+            instructions.extend([instruction as u64, operand]);
+        }
+        let instructions_len = instructions.len();
+        Ok(Self {
+            register_file,
+            instruction_pointer: 0,
+            instructions,
+            output: Vec::with_capacity(instructions_len),
+        })
+    }
+
+    // The `Register A: ...` / `Program: ...` format the puzzle comes in
+    pub fn to_puzzle_input(&self) -> String {
+        format!(
+            "Register A: {}\nRegister B: {}\nRegister C: {}\n\nProgram: {}\n",
+            self.register(Register::A),
+            self.register(Register::B),
+            self.register(Register::C),
+            self.instructions.iter().format(",")
+        )
+    }
+}
+fn resolve_operand(
+    instruction: Instruction,
+    token: &str,
+    labels: &HashMap<&str, usize>,
+) -> Result<u64, String> {
+    if instruction.takes_combo() {
+        return match token.to_ascii_lowercase().as_str() {
+            "a" => Ok(4),
+            "b" => Ok(5),
+            "c" => Ok(6),
+            "7" => Err("Combo operand 7 is reserved".to_string()),
+            digit => match digit.parse::<u64>() {
+                Ok(value @ 0..=3) => Ok(value),
+                _ => Err("Expected 0 to 3 or a register".to_string()),
+            },
+        };
+    }
+    let value = match (instruction, labels.get(token)) {
+        (Instruction::JumpNonZero, Some(&address)) => address as u64,
+        (Instruction::JumpNonZero, None) if is_label(token) => {
+            return Err("Unknown label".to_string())
+        }
+        _ => token
+            .parse::<u64>()
+            .map_err(|_| "Expected a number from 0 to 7".to_string())?,
+    };
+    // Literal operands are a single 3 bit digit, so jumps can only reach the first few instructions
+    if value > 7 {
+        return Err(format!("{value} doesn't fit in a 3 bit operand"));
+    }
+    Ok(value)
+}
+
+// Loads `.asm` files as source and anything else as puzzle input
+pub fn load_program(path: &str) -> Result<MiniPC, ParseError> {
+    let contents = read_input_file(path)?;
+    let machine = if path.ends_with(".asm") {
+        MiniPC::assemble(&contents)
+    } else {
+        MiniPC::parse(&contents)
+    };
+    machine.map_err(|e| e.with_file(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assemble() {
+        let source = "
+            .a 2024           ; The demo quine
+            loop:  adv 3
+                   OUT a
+                   jnz loop
+        ";
+        let machine = MiniPC::assemble(source).unwrap();
+        assert_eq!(machine.instructions(), [0, 3, 5, 4, 3, 0]);
+        assert_eq!(
+            machine.to_puzzle_input(),
+            "Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,4,3,0\n"
+        );
+        let bxc = MiniPC::assemble("bxc\nbxc 2").unwrap();
+        assert_eq!(bxc.instructions(), [4, 0, 4, 2]);
+        // Raw words count towards the addresses labels get
+        let raw = MiniPC::assemble(".raw 9 1 7\nend: .raw 5\njnz end").unwrap();
+        assert_eq!(raw.instructions(), [9, 1, 7, 5, 3, 3]);
+    }
+    #[test]
+    fn test_round_trip() {
+        let input = std::fs::read_to_string("input.txt").unwrap();
+        let machine = MiniPC::parse(&input).unwrap();
+        // The listing assembles back to the same program
+        let assembled = MiniPC::assemble(&machine.listing()).unwrap();
+        assert_eq!(assembled.instructions(), machine.instructions());
+        let reparsed = MiniPC::parse(&machine.to_puzzle_input()).unwrap();
+        assert_eq!(reparsed.instructions(), machine.instructions());
+        assert_eq!(reparsed.register(Register::A), 33940147);
+    }
+    #[test]
+    fn test_errors() {
+        let error = |source: &str| MiniPC::assemble(source).unwrap_err().to_string();
+        assert_eq!(
+            error("adv 3\nfoo 1"),
+            "2:1: Unknown instruction (found `foo`)"
+        );
+        assert_eq!(
+            error("out 7"),
+            "1:5: Combo operand 7 is reserved (found `7`)"
+        );
+        assert_eq!(
+            error("\n\nbxl 9"),
+            "3:5: 9 doesn't fit in a 3 bit operand (found `9`)"
+        );
+        assert_eq!(error("jnz nowhere"), "1:5: Unknown label (found `nowhere`)");
+        assert_eq!(error("bst"), "1: Missing operand for `bst`");
+        assert_eq!(
+            error("x: adv 1\nx: adv 1"),
+            "2:1: Duplicate label (found `x:`)"
+        );
+        assert_eq!(error("bst a b"), "1:7: Unexpected token (found `b`)");
+        assert_eq!(error(".raw"), "1:1: Missing words (found `.raw`)");
+        assert!(error(".raw 9 x").starts_with("1:8: "));
+        // Only the first 8 addresses can be jumped to
+        let far = "adv 1\nadv 1\nadv 1\nadv 1\nend: adv 1\njnz end";
+        assert_eq!(
+            error(far),
+            "6:5: 8 doesn't fit in a 3 bit operand (found `end`)"
+        );
+        assert_eq!(
+            error("  2: adv 1"),
+            "1:3: Address prefix doesn't match the instruction's address 0 (found `2:`)"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+// Steps a MiniPC one instruction at a time, stopping at breakpoints or when watched registers change
+use std::collections::BTreeSet;
+
+use crate::{DisassembledInstruction, MiniPC, Register, StepOutcome, VmError, ALL_REGISTERS};
+
+// State after running a single instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    pub step: usize,
+    pub instruction: DisassembledInstruction,
+    pub registers: [u64; 3], // A, B, C once the instruction has run
+    pub output: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Breakpoint(usize),         // About to run the instruction at this address
+    Watch(Register, u64, u64), // Register changed from the first value to the second
+    Halted,
+    StepLimit,
+    Error(VmError), // The instruction couldn't run, the machine is left as it was before it
+}
+
+pub struct Debugger {
+    machine: MiniPC,
+    breakpoints: BTreeSet<usize>,
+    watches: Vec<Register>,
+    steps: usize,
+}
+impl Debugger {
+    pub fn new(machine: MiniPC) -> Self {
+        Self {
+            machine,
+            breakpoints: BTreeSet::new(),
+            watches: Vec::new(),
+            steps: 0,
+        }
+    }
+    pub fn break_at(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+    pub fn watch(&mut self, register: Register) {
+        if !self.watches.contains(&register) {
+            self.watches.push(register);
+        }
+    }
+    pub fn machine(&self) -> &MiniPC {
+        &self.machine
+    }
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+    fn registers(&self) -> [u64; 3] {
+        ALL_REGISTERS.map(|register| self.machine.register(register))
+    }
+
+    // Runs one instruction, None once the program has halted
+    pub fn step(&mut self) -> Result<Option<TraceStep>, VmError> {
+        let Some(instruction) = self
+            .machine
+            .disassemble_at(self.machine.instruction_pointer())
+        else {
+            return Ok(None);
+        };
+        let output = match self.machine.run_next_instruction()? {
+            StepOutcome::Halted => return Ok(None),
+            StepOutcome::Output(value) => Some(value),
+            StepOutcome::Ran => None,
+        };
+        self.steps += 1;
+        Ok(Some(TraceStep {
+            step: self.steps,
+            instruction,
+            registers: self.registers(),
+            output,
+        }))
+    }
+    // Keeps stepping until something stops it, always running at least one instruction so
+    // continuing from a breakpoint moves past it
+    pub fn run(&mut self, max_steps: usize) -> (Vec<TraceStep>, StopReason) {
+        let mut trace = Vec::new();
+        loop {
+            if self.machine.is_halted() {
+                return (trace, StopReason::Halted);
+            }
+            let address = self.machine.instruction_pointer();
+            if !trace.is_empty() && self.breakpoints.contains(&address) {
+                return (trace, StopReason::Breakpoint(address));
+            }
+            if trace.len() >= max_steps {
+                return (trace, StopReason::StepLimit);
+            }
+            let before = self.registers();
+            let step = match self.step() {
+                Ok(Some(step)) => step,
+                Ok(None) => return (trace, StopReason::Halted),
+                Err(e) => return (trace, StopReason::Error(e)),
+            };
+            trace.push(step);
+            for &register in &self.watches {
+                let (old, new) = (before[register.index()], self.machine.register(register));
+                if old != new {
+                    return (trace, StopReason::Watch(register, old, new));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demo() -> MiniPC {
+        // adv 1, out a, jnz 0
+        MiniPC::parse("Register A: 729\n\nProgram: 0,1,5,4,3,0").unwrap()
+    }
+    #[test]
+    fn test_step_trace() {
+        let mut debugger = Debugger::new(demo());
+        let first = debugger.step().unwrap().unwrap();
+        assert_eq!(first.instruction.to_string(), "adv 1");
+        assert_eq!(first.registers, [364, 0, 0]);
+        let second = debugger.step().unwrap().unwrap();
+        assert_eq!(second.output, Some(364 % 8));
+    }
+    #[test]
+    fn test_breakpoints_and_watches() {
+        let mut debugger = Debugger::new(demo());
+        debugger.break_at(4);
+        let (trace, reason) = debugger.run(100);
+        assert_eq!((trace.len(), reason), (2, StopReason::Breakpoint(4)));
+        // Continuing runs the jump, then stops at it again on the next loop
+        let (trace, reason) = debugger.run(100);
+        assert_eq!((trace.len(), reason), (3, StopReason::Breakpoint(4)));
+
+        let mut debugger = Debugger::new(demo());
+        debugger.watch(Register::A);
+        let (trace, reason) = debugger.run(100);
+        assert_eq!(
+            (trace.len(), reason),
+            (1, StopReason::Watch(Register::A, 729, 364))
+        );
+        let (_, reason) = debugger.run(2);
+        assert_eq!(reason, StopReason::StepLimit);
+    }
+    #[test]
+    fn test_runs_to_halt() {
+        let mut debugger = Debugger::new(demo());
+        let (trace, reason) = debugger.run(usize::MAX);
+        assert_eq!(reason, StopReason::Halted);
+        assert_eq!(trace.len(), debugger.steps());
+        assert_eq!(debugger.machine().output(), [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert_eq!(debugger.step(), Ok(None));
+    }
+    #[test]
+    fn test_stops_on_errors() {
+        // out 7
+        let mut debugger = Debugger::new(MiniPC::parse("Program: 0,1,5,7").unwrap());
+        let (trace, reason) = debugger.run(100);
+        assert_eq!(trace.len(), 1);
+        assert_eq!(
+            reason,
+            StopReason::Error(VmError::ReservedOperand {
+                address: 2,
+                operand: 7
+            })
+        );
+        // Stays stuck on the bad instruction
+        assert_eq!(debugger.machine().instruction_pointer(), 2);
+    }
+}
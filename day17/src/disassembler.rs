@@ -0,0 +1,168 @@
+// Turns the numeric program back into mnemonics, e.g. `2,4` is `bst a`
+use std::fmt::Display;
+
+use crate::{Instruction, MiniPC};
+
+impl Instruction {
+    pub(crate) fn mnemonic(&self) -> &'static str {
+        match self {
+            Instruction::AShift => "adv",
+            Instruction::BxorLit => "bxl",
+            Instruction::BStore => "bst",
+            Instruction::JumpNonZero => "jnz",
+            Instruction::BxorC => "bxc",
+            Instruction::Output => "out",
+            Instruction::BShift => "bdv",
+            Instruction::CShift => "cdv",
+        }
+    }
+    // Whether the operand goes through the combo decoding rather than being used as is
+    pub(crate) fn takes_combo(&self) -> bool {
+        matches!(
+            self,
+            Instruction::AShift
+                | Instruction::BStore
+                | Instruction::Output
+                | Instruction::BShift
+                | Instruction::CShift
+        )
+    }
+}
+
+// Combo operands 0-3 are themselves, 4-6 are the registers and 7 is reserved
+fn combo_name(operand: u64) -> String {
+    match operand {
+        0..=3 => operand.to_string(),
+        4 => "a".to_string(),
+        5 => "b".to_string(),
+        6 => "c".to_string(),
+        _ => format!("?{operand}"),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisassembledInstruction {
+    pub address: usize,
+    pub opcode: u64,
+    pub operand: Option<u64>, // Missing when the program ends half way through an instruction
+}
+impl DisassembledInstruction {
+    fn instruction(&self) -> Option<Instruction> {
+        Instruction::from_opcode(self.opcode)
+    }
+    fn operand_name(&self) -> String {
+        match (self.instruction(), self.operand) {
+            (_, None) => "?".to_string(),
+            (Some(instruction), Some(operand)) if instruction.takes_combo() => combo_name(operand),
+            (_, Some(operand)) => operand.to_string(),
+        }
+    }
+    // What the instruction does in terms of the registers, e.g. `B = A % 8`
+    pub fn describe(&self) -> String {
+        let x = self.operand_name().to_uppercase();
+        match self.instruction() {
+            Some(Instruction::AShift) => format!("A = A >> {x}"),
+            Some(Instruction::BxorLit) => format!("B = B ^ {x}"),
+            Some(Instruction::BStore) => format!("B = {x} % 8"),
+            Some(Instruction::JumpNonZero) => format!("if A != 0 jump to {x}"),
+            Some(Instruction::BxorC) => "B = B ^ C".to_string(),
+            Some(Instruction::Output) => format!("output {x} % 8"),
+            Some(Instruction::BShift) => format!("B = A >> {x}"),
+            Some(Instruction::CShift) => format!("C = A >> {x}"),
+            None => "invalid opcode".to_string(),
+        }
+    }
+}
+// `bst a`, `jnz 0` and so on, bxc keeps its ignored operand so the program can be rebuilt exactly
+// Words that don't make an instruction the assembler accepts, like an unknown opcode, combo operand 7
+// or a missing operand, come out as `.raw` words instead
+impl Display for DisassembledInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.instruction(), self.operand) {
+            (Some(instruction), Some(operand))
+                if operand <= if instruction.takes_combo() { 6 } else { 7 } =>
+            {
+                write!(f, "{} {}", instruction.mnemonic(), self.operand_name())
+            }
+            (_, Some(operand)) => write!(f, ".raw {} {operand}", self.opcode),
+            (_, None) => write!(f, ".raw {}", self.opcode),
+        }
+    }
+}
+
+impl MiniPC {
+    pub fn disassemble_at(&self, address: usize) -> Option<DisassembledInstruction> {
+        let opcode = *self.instructions.get(address)?;
+        Some(DisassembledInstruction {
+            address,
+            opcode,
+            operand: self.instructions.get(address + 1).copied(),
+        })
+    }
+    pub fn disassemble(&self) -> Vec<DisassembledInstruction> {
+        (0..self.instructions.len())
+            .step_by(2)
+            .filter_map(|address| self.disassemble_at(address))
+            .collect()
+    }
+    // One instruction per line with its address and what it does
+    pub fn listing(&self) -> String {
+        self.disassemble()
+            .iter()
+            .map(|i| {
+                format!(
+                    "{:>3}: {:<8} ; {}\n",
+                    i.address,
+                    i.to_string(),
+                    i.describe()
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listing() {
+        let machine =
+            MiniPC::parse("Register A: 0\n\nProgram: 2,4,1,5,7,5,4,2,5,5,0,3,3,0").unwrap();
+        let mnemonics: Vec<String> = machine
+            .disassemble()
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            mnemonics,
+            ["bst a", "bxl 5", "cdv b", "bxc 2", "out b", "adv 3", "jnz 0"]
+        );
+        let listing = machine.listing();
+        assert!(listing.starts_with("  0: bst a    ; B = A % 8\n"));
+        assert!(listing.contains(" 12: jnz 0    ; if A != 0 jump to 0\n"));
+    }
+    #[test]
+    fn test_odd_programs() {
+        let machine = MiniPC::parse("Program: 0,7,9,1,5").unwrap();
+        let mnemonics: Vec<String> = machine
+            .disassemble()
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(mnemonics, [".raw 0 7", ".raw 9 1", ".raw 5"]);
+        assert!(machine
+            .listing()
+            .contains("  2: .raw 9 1 ; invalid opcode\n"));
+        // And they still assemble back to the same words
+        for program in ["0,7,9,1,5", "1,9,3,8,4", "2,4,0,7,5"] {
+            let machine = MiniPC::parse(&format!("Program: {program}")).unwrap();
+            let assembled = MiniPC::assemble(&machine.listing()).unwrap();
+            assert_eq!(
+                assembled.instructions(),
+                machine.instructions(),
+                "{program}"
+            );
+        }
+    }
+}
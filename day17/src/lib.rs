@@ -0,0 +1,384 @@
+use std::{fmt::Display, str::FromStr};
+
+use itertools::Itertools;
+use shared::{Answer, ParseError, Solution};
+
+mod assembler;
+mod debugger;
+mod disassembler;
+mod quine;
+pub mod symbolic;
+pub use assembler::*;
+pub use debugger::*;
+pub use disassembler::*;
+pub use quine::*;
+
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    AShift = 0,      // Div of A and 2^Combo Op
+    BxorLit = 1,     //XOR of B and literal
+    BStore = 2,      // Combo Op %8 and store to B
+    JumpNonZero = 3, // If A!=0, jump to literal operand
+    BxorC = 4,       // Bitwise XOR of B and C, stores into B, ignores operand
+    Output = 5,      // calculates the value of its combo operand % 8 and outputs it
+    BShift = 6,      // Div of B and 2^Combo Op
+    CShift = 7,      // Div of C and 2^Combo Op
+}
+
+impl Instruction {
+    fn from_opcode(opcode: u64) -> Option<Self> {
+        match opcode {
+            0 => Some(Instruction::AShift),
+            1 => Some(Instruction::BxorLit),
+            2 => Some(Instruction::BStore),
+            3 => Some(Instruction::JumpNonZero),
+            4 => Some(Instruction::BxorC),
+            5 => Some(Instruction::Output),
+            6 => Some(Instruction::BShift),
+            7 => Some(Instruction::CShift),
+            _ => None,
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepOutcome {
+    Ran,
+    Output(u64),
+    Halted, // Nothing was run, the instruction pointer is past the end
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VmError {
+    InvalidOpcode { address: usize, opcode: u64 },
+    ReservedOperand { address: usize, operand: u64 }, // Combo operand 7, or anything above it
+    TruncatedInstruction { address: usize },          // An opcode with no operand after it
+    ShiftOverflow { address: usize, amount: u64 },    // Dividing by 2^64 or more
+    StepLimit { max_steps: usize },                   // Most likely an infinite loop
+}
+impl Display for VmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::InvalidOpcode { address, opcode } => {
+                write!(f, "Invalid opcode {opcode} at {address}")
+            }
+            VmError::ReservedOperand { address, operand } => {
+                write!(f, "Reserved combo operand {operand} at {address}")
+            }
+            VmError::TruncatedInstruction { address } => {
+                write!(f, "Instruction at {address} is missing its operand")
+            }
+            VmError::ShiftOverflow { address, amount } => {
+                write!(f, "Shift by {amount} at {address} overflows")
+            }
+            VmError::StepLimit { max_steps } => {
+                write!(f, "Still running after {max_steps} steps")
+            }
+        }
+    }
+}
+impl std::error::Error for VmError {}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+pub const ALL_REGISTERS: [Register; 3] = [Register::A, Register::B, Register::C];
+impl Register {
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+impl Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Register::A => "A",
+            Register::B => "B",
+            Register::C => "C",
+        };
+        f.pad(name)
+    }
+}
+impl FromStr for Register {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "a" | "A" => Ok(Register::A),
+            "b" | "B" => Ok(Register::B),
+            "c" | "C" => Ok(Register::C),
+            _ => Err(ParseError::new(format!("Unknown register `{s}`"))),
+        }
+    }
+}
+// A, B and C, concrete values when running and bit expressions when running symbolically
+#[derive(Debug, Default, Clone)]
+struct RegisterFile<T = u64> {
+    registers: [T; 3],
+}
+#[derive(Debug, Clone)]
+pub struct MiniPC {
+    register_file: RegisterFile,
+    instruction_pointer: usize,
+    instructions: Vec<u64>,
+    output: Vec<u64>,
+}
+impl MiniPC {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut register_file = RegisterFile::default();
+        let mut instructions = Vec::new();
+        for line in input.lines() {
+            if line.is_empty() {
+                continue;
+            }
+            //SPlit by colon to get name / value
+            let (name, value) = line
+                .split_once(":")
+                .ok_or_else(|| ParseError::new(format!("Unknown line {line}")))?;
+            match name {
+                "Register A" => register_file.registers[0] = value.trim().parse()?,
+                "Register B" => register_file.registers[1] = value.trim().parse()?,
+                "Register C" => register_file.registers[2] = value.trim().parse()?,
+                "Program" => {
+                    instructions = value
+                        .split(",")
+                        .map(|x| x.trim().parse())
+                        .collect::<Result<_, _>>()?;
+                }
+                _ => return Err(ParseError::new(format!("Unknown line {name}"))),
+            }
+        }
+        let instructions_len = instructions.len();
+        Ok(Self {
+            register_file,
+            instruction_pointer: 0,
+            instructions,
+            output: Vec::with_capacity(instructions_len),
+        })
+    }
+    // Runs one instruction, never panicking however broken the program is
+    pub fn run_next_instruction(&mut self) -> Result<StepOutcome, VmError> {
+        if self.is_halted() {
+            return Ok(StepOutcome::Halted);
+        }
+        let address = self.instruction_pointer;
+        let opcode = self.instructions[address];
+        let instruction =
+            Instruction::from_opcode(opcode).ok_or(VmError::InvalidOpcode { address, opcode })?;
+        let operand = *self
+            .instructions
+            .get(address + 1)
+            .ok_or(VmError::TruncatedInstruction { address })?;
+        let mut outcome = StepOutcome::Ran;
+        match instruction {
+            Instruction::BxorLit => {
+                // BXL: XOR of B and literal
+                self.register_file.registers[1] ^= operand;
+            }
+            Instruction::BStore => {
+                // BST: Combo Op %8 and store to B
+                self.register_file.registers[1] = self.get_combo_op(operand)? % 8;
+            }
+            Instruction::JumpNonZero => {
+                // JNZ: If A!=0, jump to literal operand
+                if self.register_file.registers[0] != 0 {
+                    self.instruction_pointer = operand as usize;
+                    return Ok(StepOutcome::Ran); // Do not increment program counter
+                }
+            }
+            Instruction::BxorC => {
+                // BXC: Bitwise XOR of B and C, stores into B, ignores operand
+                self.register_file.registers[1] ^= self.register_file.registers[2];
+            }
+            Instruction::Output => {
+                // OUT: calculates the value of its combo operand % 8 and outputs it
+                let value = self.get_combo_op(operand)? % 8;
+                self.output.push(value);
+                outcome = StepOutcome::Output(value);
+            }
+            Instruction::AShift => {
+                // ADV: Div of A and 2^Combo Op
+                self.register_file.registers[0] = self.shift_a(operand)?;
+            }
+            Instruction::BShift => {
+                // BDV: Div of B and 2^Combo Op
+                self.register_file.registers[1] = self.shift_a(operand)?;
+            }
+            Instruction::CShift => {
+                // CDV: Div of C and 2^Combo Op
+                self.register_file.registers[2] = self.shift_a(operand)?;
+            }
+        }
+        self.instruction_pointer += 2; // Next step
+        Ok(outcome)
+    }
+    // Runs until the program halts, giving up after `max_steps` instructions in case it never does
+    pub fn run(&mut self, max_steps: usize) -> Result<(), VmError> {
+        for _ in 0..max_steps {
+            if self.run_next_instruction()? == StepOutcome::Halted {
+                return Ok(());
+            }
+        }
+        if self.is_halted() {
+            Ok(())
+        } else {
+            Err(VmError::StepLimit { max_steps })
+        }
+    }
+
+    // A / 2^combo, the divisor only fits in a u64 up to 2^63
+    fn shift_a(&self, operand: u64) -> Result<u64, VmError> {
+        let amount = self.get_combo_op(operand)?;
+        u32::try_from(amount)
+            .ok()
+            .and_then(|amount| 2_u64.checked_pow(amount))
+            .map(|divisor| self.register_file.registers[0] / divisor)
+            .ok_or(VmError::ShiftOverflow {
+                address: self.instruction_pointer,
+                amount,
+            })
+    }
+    fn get_combo_op(&self, op: u64) -> Result<u64, VmError> {
+        match op {
+            0 => Ok(0),                               // Literally 0
+            1 => Ok(1),                               // Literally 1
+            2 => Ok(2),                               // Literally 2
+            3 => Ok(3),                               // Literally 3
+            4 => Ok(self.register_file.registers[0]), // Reg A
+            5 => Ok(self.register_file.registers[1]), // Reg B
+            6 => Ok(self.register_file.registers[2]), // Reg C
+            _ => Err(VmError::ReservedOperand {
+                address: self.instruction_pointer,
+                operand: op,
+            }),
+        }
+    }
+    pub fn is_halted(&self) -> bool {
+        self.instruction_pointer >= self.instructions.len()
+    }
+    pub fn register(&self, register: Register) -> u64 {
+        self.register_file.registers[register.index()]
+    }
+    pub fn set_register(&mut self, register: Register, value: u64) {
+        self.register_file.registers[register.index()] = value;
+    }
+    pub fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+    pub fn instructions(&self) -> &[u64] {
+        &self.instructions
+    }
+    pub fn output(&self) -> &[u64] {
+        &self.output
+    }
+}
+shared::register_day!(17, Day17);
+
+pub struct Day17;
+impl Solution for Day17 {
+    type Input = MiniPC;
+    type Config = Day17Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        MiniPC::parse(input)
+    }
+    fn part_a(input: &Self::Input, config: &Day17Config) -> Answer {
+        match part_a(input, config.max_steps) {
+            Ok(output) => output.into(),
+            Err(e) => e.to_string().into(),
+        }
+    }
+    fn part_b(input: &Self::Input, _config: &Day17Config) -> Answer {
+        match part_b(input) {
+            Some(a) => a.into(),
+            None => "No quine".into(),
+        }
+    }
+}
+pub struct Day17Config {
+    pub max_steps: usize, // Instructions part A runs before deciding the program loops forever
+}
+impl Default for Day17Config {
+    fn default() -> Self {
+        Self {
+            max_steps: 1_000_000,
+        }
+    }
+}
+fn part_a(machine: &MiniPC, max_steps: usize) -> Result<String, VmError> {
+    let mut machine = machine.clone();
+    machine.run(max_steps)?;
+    Ok(format!("{}", machine.output.iter().format(",")))
+}
+fn part_b(machine: &MiniPC) -> Option<u64> {
+    find_quine(machine)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day17::solve_file("test.txt", Part::A);
+        assert_eq!(results, "4,6,3,5,6,3,5,2,1,0");
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day17::solve_file("input.txt", Part::A);
+        assert_eq!(results, "2,7,6,5,6,0,2,3,1");
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day17::solve_file("test2.txt", Part::B);
+        assert_eq!(results, 117440);
+    }
+    #[test]
+    fn test_vm_errors() {
+        let run = |input: &str| MiniPC::parse(input).unwrap().run(1_000);
+        assert_eq!(
+            run("Program: 0,1,8,0"),
+            Err(VmError::InvalidOpcode {
+                address: 2,
+                opcode: 8
+            })
+        );
+        assert_eq!(
+            run("Program: 0,1,5"),
+            Err(VmError::TruncatedInstruction { address: 2 })
+        );
+        assert_eq!(
+            run("Program: 2,7"),
+            Err(VmError::ReservedOperand {
+                address: 0,
+                operand: 7
+            })
+        );
+        assert_eq!(
+            run("Register B: 64\n\nProgram: 6,5"),
+            Err(VmError::ShiftOverflow {
+                address: 0,
+                amount: 64
+            })
+        );
+        // jnz 0 with A never changing
+        assert_eq!(
+            run("Register A: 1\n\nProgram: 3,0"),
+            Err(VmError::StepLimit { max_steps: 1_000 })
+        );
+        assert_eq!(run("Register B: 63\n\nProgram: 6,5"), Ok(()));
+    }
+    #[test]
+    fn test_step_budget() {
+        let looping = Day17::parse("Register A: 1\n\nProgram: 3,0").unwrap();
+        let config = Day17Config { max_steps: 10 };
+        assert_eq!(
+            Day17::solve(&looping, Part::A, &config),
+            "Still running after 10 steps"
+        );
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day17::solve_file("input.txt", Part::B);
+        assert_eq!(results, 107416870455451);
+    }
+}
@@ -0,0 +1,83 @@
+// Finds the smallest register A that makes a program print itself
+// Only handles the usual shape of program: one loop that outputs and shifts A right by 3 before
+// jumping back to the start, so each output digit only depends on A's remaining bits
+use crate::{Instruction, MiniPC, Register};
+
+// A program that would loop forever with this A isn't a quine anyway
+const MAX_STEPS: usize = 100_000;
+
+// Whether the program is a single loop that shifts A by 3 each time round and outputs something
+fn is_shift_by_3_loop(machine: &MiniPC) -> bool {
+    let instructions = machine.disassemble();
+    let Some((last, body)) = instructions.split_last() else {
+        return false;
+    };
+    let count = |instruction: Instruction| {
+        body.iter()
+            .filter(|i| i.opcode == instruction as u64)
+            .count()
+    };
+    let shifts_by_3 = body
+        .iter()
+        .filter(|i| i.opcode == Instruction::AShift as u64)
+        .all(|i| i.operand == Some(3));
+    last.opcode == Instruction::JumpNonZero as u64
+        && last.operand == Some(0)
+        && count(Instruction::JumpNonZero) == 0
+        && count(Instruction::AShift) == 1
+        && shifts_by_3
+        && count(Instruction::Output) >= 1
+}
+
+// Output from running the program with A set, None if it fails or doesn't halt in time
+fn run_with_a(machine: &MiniPC, a: u64) -> Option<Vec<u64>> {
+    let mut machine = machine.clone();
+    machine.set_register(Register::A, a);
+    machine.run(MAX_STEPS).ok()?;
+    Some(machine.output)
+}
+
+pub fn find_quine(machine: &MiniPC) -> Option<u64> {
+    if !is_shift_by_3_loop(machine) {
+        return None;
+    }
+    // Build A up from its top 3 bits, which only affect the last output, so after adding the
+    // nth group of bits the output has to match the last n digits of the program
+    fn search(machine: &MiniPC, digits_matched: usize, accumulator: u64) -> Option<u64> {
+        let program = &machine.instructions;
+        if digits_matched == program.len() {
+            return Some(accumulator);
+        }
+        // Smaller digits first, as the top bits are decided first this finds the smallest A
+        (0..8).find_map(|digit| {
+            let a = accumulator.checked_mul(8)? | digit;
+            let output = run_with_a(machine, a)?;
+            if output == program[program.len() - digits_matched - 1..] {
+                search(machine, digits_matched + 1, a)
+            } else {
+                None
+            }
+        })
+    }
+    let a = search(machine, 0, 0)?;
+    // Check the whole thing forwards, rather than trusting the loop structure
+    (run_with_a(machine, a)? == machine.instructions).then_some(a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_programs() {
+        // Part A's demo shifts by 1
+        let machine = MiniPC::parse("Register A: 729\n\nProgram: 0,1,5,4,3,0").unwrap();
+        assert_eq!(find_quine(&machine), None);
+        // Never outputs
+        let machine = MiniPC::parse("Register A: 729\n\nProgram: 0,3,3,0").unwrap();
+        assert_eq!(find_quine(&machine), None);
+        // Right shape, but no A can print it
+        let machine = MiniPC::parse("Register A: 729\n\nProgram: 0,3,5,0,3,0").unwrap();
+        assert_eq!(find_quine(&machine), None);
+    }
+}
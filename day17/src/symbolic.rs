@@ -0,0 +1,603 @@
+// Runs a MiniPC with register A unknown, tracking every register bit as an expression over A's bits
+// Each output digit then becomes a constraint on A, which a small solver can answer
+// "which A values print this?" for any program, not just ones shaped like the puzzle's
+use std::{collections::BTreeSet, fmt::Display, rc::Rc};
+
+use crate::{Instruction, MiniPC, Register, RegisterFile};
+
+const WORD_BITS: usize = 64;
+// Past this trying every value takes far too long
+const BRUTE_FORCE_BITS: usize = 32;
+// Symbolic paths can't be cut short by a halt check, so give up rather than unroll forever
+const MAX_STEPS: usize = 10_000;
+
+// A single bit, in terms of the bits of A, `Var(0)` being the lowest
+// Built through the constructors below, which fold constants and cancel out repeats as they go
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expr {
+    Const(bool),
+    Var(usize),
+    Not(Rc<Expr>),
+    And(Rc<Expr>, Rc<Expr>),
+    Or(Rc<Expr>, Rc<Expr>),
+    Xor(Rc<Expr>, Rc<Expr>),
+}
+impl Expr {
+    pub fn negate(e: Expr) -> Expr {
+        match e {
+            Expr::Const(b) => Expr::Const(!b),
+            Expr::Not(inner) => (*inner).clone(),
+            e => Expr::Not(Rc::new(e)),
+        }
+    }
+    pub fn and(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(false), _) | (_, Expr::Const(false)) => Expr::Const(false),
+            (Expr::Const(true), e) | (e, Expr::Const(true)) => e,
+            (a, b) if a == b => a,
+            (a, b) if a.is_negation_of(&b) => Expr::Const(false),
+            (a, b) => Expr::And(Rc::new(a), Rc::new(b)),
+        }
+    }
+    pub fn or(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(true), _) | (_, Expr::Const(true)) => Expr::Const(true),
+            (Expr::Const(false), e) | (e, Expr::Const(false)) => e,
+            (a, b) if a == b => a,
+            (a, b) if a.is_negation_of(&b) => Expr::Const(true),
+            (a, b) => Expr::Or(Rc::new(a), Rc::new(b)),
+        }
+    }
+    pub fn xor(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(false), e) | (e, Expr::Const(false)) => e,
+            (Expr::Const(true), e) | (e, Expr::Const(true)) => Expr::negate(e),
+            (a, b) if a == b => Expr::Const(false),
+            (a, b) if a.is_negation_of(&b) => Expr::Const(true),
+            // Pull negations out, so !x ^ y and x ^ !y line up with each other
+            (Expr::Not(a), b) | (b, Expr::Not(a)) => Expr::negate(Expr::xor((*a).clone(), b)),
+            (a, b) => Expr::Xor(Rc::new(a), Rc::new(b)),
+        }
+    }
+    // True when the bit equals `value`
+    pub fn equals(e: Expr, value: bool) -> Expr {
+        if value {
+            e
+        } else {
+            Expr::negate(e)
+        }
+    }
+    fn is_negation_of(&self, other: &Expr) -> bool {
+        matches!(self, Expr::Not(inner) if **inner == *other)
+            || matches!(other, Expr::Not(inner) if **inner == *self)
+    }
+    pub fn as_const(&self) -> Option<bool> {
+        match self {
+            Expr::Const(b) => Some(*b),
+            _ => None,
+        }
+    }
+    // Value under a partial assignment of A's bits, None if it still depends on an unassigned one
+    pub fn eval(&self, assignment: &[Option<bool>]) -> Option<bool> {
+        match self {
+            Expr::Const(b) => Some(*b),
+            Expr::Var(i) => assignment.get(*i).copied().flatten(),
+            Expr::Not(e) => e.eval(assignment).map(|b| !b),
+            Expr::And(a, b) => match (a.eval(assignment), b.eval(assignment)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Expr::Or(a, b) => match (a.eval(assignment), b.eval(assignment)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            Expr::Xor(a, b) => Some(a.eval(assignment)? ^ b.eval(assignment)?),
+        }
+    }
+    // Substitutes the assigned bits and simplifies what's left
+    pub fn simplify(&self, assignment: &[Option<bool>]) -> Expr {
+        match self {
+            Expr::Const(_) => self.clone(),
+            Expr::Var(i) => match assignment.get(*i).copied().flatten() {
+                Some(b) => Expr::Const(b),
+                None => self.clone(),
+            },
+            Expr::Not(e) => Expr::negate(e.simplify(assignment)),
+            Expr::And(a, b) => Expr::and(a.simplify(assignment), b.simplify(assignment)),
+            Expr::Or(a, b) => Expr::or(a.simplify(assignment), b.simplify(assignment)),
+            Expr::Xor(a, b) => Expr::xor(a.simplify(assignment), b.simplify(assignment)),
+        }
+    }
+    pub fn variables(&self) -> BTreeSet<usize> {
+        let mut variables = BTreeSet::new();
+        let mut stack = vec![self];
+        while let Some(e) = stack.pop() {
+            match e {
+                Expr::Const(_) => {}
+                Expr::Var(i) => {
+                    variables.insert(*i);
+                }
+                Expr::Not(e) => stack.push(e),
+                Expr::And(a, b) | Expr::Or(a, b) | Expr::Xor(a, b) => {
+                    stack.push(a);
+                    stack.push(b);
+                }
+            }
+        }
+        variables
+    }
+}
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Const(b) => write!(f, "{}", *b as u8),
+            Expr::Var(i) => write!(f, "a{i}"),
+            Expr::Not(e) => write!(f, "!{e}"),
+            Expr::And(a, b) => write!(f, "({a} & {b})"),
+            Expr::Or(a, b) => write!(f, "({a} | {b})"),
+            Expr::Xor(a, b) => write!(f, "({a} ^ {b})"),
+        }
+    }
+}
+
+// A register's worth of bits, lowest first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitVec {
+    bits: Vec<Expr>,
+}
+impl BitVec {
+    pub fn constant(value: u64) -> Self {
+        Self {
+            bits: (0..WORD_BITS)
+                .map(|i| Expr::Const(value >> i & 1 == 1))
+                .collect(),
+        }
+    }
+    // The unknown input, with everything above `input_bits` zero
+    pub fn input(input_bits: usize) -> Self {
+        Self {
+            bits: (0..WORD_BITS)
+                .map(|i| {
+                    if i < input_bits {
+                        Expr::Var(i)
+                    } else {
+                        Expr::Const(false)
+                    }
+                })
+                .collect(),
+        }
+    }
+    pub fn bit(&self, i: usize) -> &Expr {
+        &self.bits[i]
+    }
+    pub fn as_const(&self) -> Option<u64> {
+        self.bits.iter().enumerate().try_fold(0, |value, (i, bit)| {
+            Some(value | (bit.as_const()? as u64) << i)
+        })
+    }
+    pub fn xor(&self, other: &BitVec) -> Self {
+        Self {
+            bits: self
+                .bits
+                .iter()
+                .zip(&other.bits)
+                .map(|(a, b)| Expr::xor(a.clone(), b.clone()))
+                .collect(),
+        }
+    }
+    pub fn mod_8(&self) -> Self {
+        Self {
+            bits: (0..WORD_BITS)
+                .map(|i| {
+                    if i < 3 {
+                        self.bits[i].clone()
+                    } else {
+                        Expr::Const(false)
+                    }
+                })
+                .collect(),
+        }
+    }
+    fn shr_const(&self, amount: usize) -> Self {
+        Self {
+            bits: (0..WORD_BITS)
+                .map(|i| {
+                    self.bits
+                        .get(i + amount)
+                        .cloned()
+                        .unwrap_or(Expr::Const(false))
+                })
+                .collect(),
+        }
+    }
+    // Shift right by an amount that may itself be unknown, picking between every shift it could be
+    pub fn shr(&self, amount: &BitVec) -> Result<Self, SymbolicError> {
+        if let Some(amount) = amount.as_const() {
+            return usize::try_from(amount)
+                .ok()
+                .filter(|&amount| amount < WORD_BITS)
+                .map(|amount| self.shr_const(amount))
+                .ok_or(SymbolicError::ShiftTooWide);
+        }
+        // Only shifts up to 63 are handled, so the rest of the amount has to be known to be zero
+        if amount.bits[6..]
+            .iter()
+            .any(|bit| bit.as_const() != Some(false))
+        {
+            return Err(SymbolicError::ShiftTooWide);
+        }
+        let mut result = BitVec::constant(0);
+        for shift in 0..WORD_BITS {
+            // The condition for the amount being exactly `shift`
+            let selected = (0..6).fold(Expr::Const(true), |cond, i| {
+                Expr::and(
+                    cond,
+                    Expr::equals(amount.bits[i].clone(), shift >> i & 1 == 1),
+                )
+            });
+            if selected == Expr::Const(false) {
+                continue;
+            }
+            let shifted = self.shr_const(shift);
+            for (bit, shifted_bit) in result.bits.iter_mut().zip(shifted.bits) {
+                *bit = Expr::or(bit.clone(), Expr::and(selected.clone(), shifted_bit));
+            }
+        }
+        Ok(result)
+    }
+    // True when every bit is zero
+    pub fn is_zero(&self) -> Expr {
+        Expr::negate(
+            self.bits
+                .iter()
+                .fold(Expr::Const(false), |any, bit| Expr::or(any, bit.clone())),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolicError {
+    InvalidOpcode(usize),
+    ReservedOperand(usize),
+    ShiftTooWide, // Shifting by 64 or more, or by something that could be
+    StepLimit,
+    TooManyBits(usize), // To try every value of
+}
+impl Display for SymbolicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolicError::InvalidOpcode(address) => write!(f, "Invalid opcode at {address}"),
+            SymbolicError::ReservedOperand(address) => {
+                write!(f, "Reserved combo operand 7 at {address}")
+            }
+            SymbolicError::ShiftTooWide => write!(f, "Shift could be 64 bits or more"),
+            SymbolicError::StepLimit => write!(f, "Gave up after {MAX_STEPS} steps"),
+            SymbolicError::TooManyBits(bits) => write!(
+                f,
+                "Can't try every value of {bits} bits, at most {BRUTE_FORCE_BITS} can be"
+            ),
+        }
+    }
+}
+
+// Something that has to hold for A to give the wanted output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    pub label: String,
+    pub expr: Expr,
+}
+impl Display for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.label, self.expr)
+    }
+}
+
+struct SymbolicMachine<'a> {
+    machine: &'a MiniPC,
+    register_file: RegisterFile<BitVec>,
+    instruction_pointer: usize,
+}
+impl SymbolicMachine<'_> {
+    fn register(&self, register: Register) -> &BitVec {
+        &self.register_file.registers[register.index()]
+    }
+    fn set(&mut self, register: Register, value: BitVec) {
+        self.register_file.registers[register.index()] = value;
+    }
+    fn combo(&self, operand: u64) -> Result<BitVec, SymbolicError> {
+        match operand {
+            0..=3 => Ok(BitVec::constant(operand)),
+            4 => Ok(self.register(Register::A).clone()),
+            5 => Ok(self.register(Register::B).clone()),
+            6 => Ok(self.register(Register::C).clone()),
+            _ => Err(SymbolicError::ReservedOperand(self.instruction_pointer)),
+        }
+    }
+}
+
+// Runs the program with the bottom `input_bits` of A unknown, and returns what has to hold for it
+// to print `target`
+// Where a jump depends on A the path taken is the one that keeps looping until enough has been
+// printed, with the branch condition added as a constraint of its own
+pub fn constraints_for_output(
+    machine: &MiniPC,
+    input_bits: usize,
+    target: &[u64],
+) -> Result<Vec<Constraint>, SymbolicError> {
+    let mut state = SymbolicMachine {
+        machine,
+        register_file: RegisterFile {
+            registers: [
+                BitVec::input(input_bits.min(WORD_BITS)),
+                BitVec::constant(machine.register(Register::B)),
+                BitVec::constant(machine.register(Register::C)),
+            ],
+        },
+        instruction_pointer: 0,
+    };
+    let mut constraints = Vec::new();
+    let mut outputs = 0;
+    let program = &state.machine.instructions;
+    for _ in 0..MAX_STEPS {
+        let address = state.instruction_pointer;
+        let Some(&opcode) = program.get(address) else {
+            if outputs < target.len() {
+                constraints.push(Constraint {
+                    label: format!("halts after {outputs} of {} outputs", target.len()),
+                    expr: Expr::Const(false),
+                });
+            }
+            return Ok(constraints);
+        };
+        let instruction =
+            Instruction::from_opcode(opcode).ok_or(SymbolicError::InvalidOpcode(address))?;
+        // A missing operand reads as 0, which is never worse than guessing
+        let operand = program.get(address + 1).copied().unwrap_or(0);
+        let a = state.register(Register::A).clone();
+        match instruction {
+            Instruction::AShift => state.set(Register::A, a.shr(&state.combo(operand)?)?),
+            Instruction::BxorLit => {
+                let b = state.register(Register::B).xor(&BitVec::constant(operand));
+                state.set(Register::B, b);
+            }
+            Instruction::BStore => state.set(Register::B, state.combo(operand)?.mod_8()),
+            Instruction::JumpNonZero => {
+                let is_zero = a.is_zero();
+                let jump = match is_zero.as_const() {
+                    Some(zero) => !zero,
+                    None => {
+                        let jump = outputs < target.len();
+                        constraints.push(Constraint {
+                            label: format!("A {} 0 at {address}", if jump { "!=" } else { "==" }),
+                            expr: Expr::equals(is_zero, !jump),
+                        });
+                        jump
+                    }
+                };
+                if jump {
+                    state.instruction_pointer = operand as usize;
+                    continue;
+                }
+            }
+            Instruction::BxorC => {
+                let b = state.register(Register::B).xor(state.register(Register::C));
+                state.set(Register::B, b);
+            }
+            Instruction::Output => {
+                let value = state.combo(operand)?;
+                let expr = match target.get(outputs) {
+                    Some(&digit) => (0..3).fold(Expr::Const(true), |all, i| {
+                        Expr::and(all, Expr::equals(value.bit(i).clone(), digit >> i & 1 == 1))
+                    }),
+                    None => Expr::Const(false),
+                };
+                constraints.push(Constraint {
+                    label: match target.get(outputs) {
+                        Some(digit) => format!("output {outputs} == {digit}"),
+                        None => format!("output {outputs} is past the end"),
+                    },
+                    expr,
+                });
+                outputs += 1;
+            }
+            Instruction::BShift => state.set(Register::B, a.shr(&state.combo(operand)?)?),
+            Instruction::CShift => state.set(Register::C, a.shr(&state.combo(operand)?)?),
+        }
+        state.instruction_pointer += 2;
+    }
+    Err(SymbolicError::StepLimit)
+}
+
+// Tries every value of the input bits, smallest first, only sensible for a handful of bits
+pub fn solve_brute_force(
+    constraints: &[Constraint],
+    input_bits: usize,
+    limit: usize,
+) -> Result<Vec<u64>, SymbolicError> {
+    if input_bits > BRUTE_FORCE_BITS {
+        return Err(SymbolicError::TooManyBits(input_bits));
+    }
+    Ok((0..1_u64 << input_bits)
+        .filter(|&a| {
+            let assignment: Vec<Option<bool>> =
+                (0..input_bits).map(|i| Some(a >> i & 1 == 1)).collect();
+            constraints
+                .iter()
+                .all(|c| c.expr.eval(&assignment) == Some(true))
+        })
+        .take(limit)
+        .collect())
+}
+
+// Backtracking search deciding A's bits from the top down, trying 0 before 1 so solutions come out
+// smallest first. A constraint that is already false cuts the search off, and one left with a
+// single undecided bit forces that bit. A is only 64 bits, so any more than that are ignored
+pub fn solve(constraints: &[Constraint], input_bits: usize, limit: usize) -> Vec<u64> {
+    let input_bits = input_bits.min(WORD_BITS);
+    struct Search<'a> {
+        constraints: Vec<(&'a Expr, Vec<usize>)>, // With the bits each one depends on
+        assignment: Vec<Option<bool>>,
+        solutions: Vec<u64>,
+        limit: usize,
+    }
+    impl Search<'_> {
+        // Forces single undecided bits, returning the bits set or None on a conflict
+        fn propagate(&mut self) -> Option<Vec<usize>> {
+            let mut forced = Vec::new();
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for (expr, variables) in &self.constraints {
+                    let mut unassigned =
+                        variables.iter().filter(|&&v| self.assignment[v].is_none());
+                    match (
+                        expr.eval(&self.assignment),
+                        unassigned.next(),
+                        unassigned.next(),
+                    ) {
+                        (Some(false), _, _) => {
+                            forced
+                                .iter()
+                                .for_each(|&v: &usize| self.assignment[v] = None);
+                            return None;
+                        }
+                        (None, Some(&v), None) => {
+                            // Only forced if one of the values fails, it may not matter at all
+                            let fails = [false, true].map(|value| {
+                                self.assignment[v] = Some(value);
+                                expr.eval(&self.assignment) == Some(false)
+                            });
+                            self.assignment[v] = None;
+                            if fails[0] != fails[1] {
+                                self.assignment[v] = Some(fails[0]);
+                                forced.push(v);
+                                changed = true;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Some(forced)
+        }
+        fn search(&mut self, bit: Option<usize>) {
+            let Some(forced) = self.propagate() else {
+                return;
+            };
+            // Skip past anything propagation has already decided
+            let mut next = bit;
+            while let Some(b) = next.filter(|&b| self.assignment[b].is_some()) {
+                next = b.checked_sub(1);
+            }
+            match next {
+                None => {
+                    let a = self
+                        .assignment
+                        .iter()
+                        .enumerate()
+                        .fold(0, |a, (i, bit)| a | (bit.unwrap() as u64) << i);
+                    self.solutions.push(a);
+                }
+                Some(b) => {
+                    for value in [false, true] {
+                        if self.solutions.len() >= self.limit {
+                            break;
+                        }
+                        self.assignment[b] = Some(value);
+                        self.search(b.checked_sub(1));
+                    }
+                    self.assignment[b] = None;
+                }
+            }
+            forced.iter().for_each(|&v| self.assignment[v] = None);
+        }
+    }
+    let mut search = Search {
+        constraints: constraints
+            .iter()
+            .map(|c| (&c.expr, c.expr.variables().into_iter().collect()))
+            .collect(),
+        assignment: vec![None; input_bits],
+        solutions: Vec::new(),
+        limit,
+    };
+    if limit > 0 {
+        search.search(input_bits.checked_sub(1));
+    }
+    search.solutions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simplification() {
+        let a0 = Expr::Var(0);
+        let a1 = Expr::Var(1);
+        assert_eq!(Expr::xor(a0.clone(), a0.clone()), Expr::Const(false));
+        assert_eq!(
+            Expr::and(a0.clone(), Expr::negate(a0.clone())),
+            Expr::Const(false)
+        );
+        assert_eq!(Expr::negate(Expr::negate(a1.clone())), a1);
+        assert_eq!(
+            Expr::xor(Expr::negate(a0.clone()), a1.clone()),
+            Expr::negate(Expr::xor(a0.clone(), a1.clone()))
+        );
+        let e = Expr::or(Expr::and(a0.clone(), a1.clone()), Expr::Const(false));
+        assert_eq!(e.to_string(), "(a0 & a1)");
+        assert_eq!(e.simplify(&[Some(true), None]), a1);
+    }
+    #[test]
+    fn test_symbolic_shift() {
+        // A >> (A % 8) for every 6 bit A
+        let a = BitVec::input(6);
+        let shifted = a.shr(&a.mod_8()).unwrap();
+        for value in 0..64_u64 {
+            let assignment: Vec<Option<bool>> = (0..6).map(|i| Some(value >> i & 1 == 1)).collect();
+            let result = (0..6).fold(0, |acc, i| {
+                acc | (shifted.bit(i).eval(&assignment).unwrap() as u64) << i
+            });
+            assert_eq!(result, value >> (value % 8));
+        }
+        // A 7 bit amount could be 64 or more
+        let wide = BitVec::input(7);
+        assert_eq!(a.shr(&wide), Err(SymbolicError::ShiftTooWide));
+    }
+    #[test]
+    fn test_demo_quine() {
+        let machine = MiniPC::parse("Register A: 2024\n\nProgram: 0,3,5,4,3,0").unwrap();
+        let constraints = constraints_for_output(&machine, 18, &machine.instructions).unwrap();
+        assert_eq!(constraints[0].label, "output 0 == 0");
+        assert_eq!(solve(&constraints, 18, 1), [117440]);
+        assert_eq!(solve_brute_force(&constraints, 18, 1), Ok(vec![117440]));
+        // Wider than A, the extra bits are never set
+        let constraints = constraints_for_output(&machine, 100, &machine.instructions).unwrap();
+        assert_eq!(solve(&constraints, 100, 1), [117440]);
+        assert_eq!(
+            solve_brute_force(&constraints, 64, 1),
+            Err(SymbolicError::TooManyBits(64))
+        );
+    }
+    #[test]
+    fn test_which_a_prints() {
+        // bst a, out b, adv 3, jnz 0: prints A in octal from the bottom digit up
+        let machine = MiniPC::parse("Program: 2,4,5,5,0,3,3,0").unwrap();
+        let constraints = constraints_for_output(&machine, 9, &[1, 2]).unwrap();
+        assert_eq!(solve(&constraints, 9, 10), [0o21]);
+        assert_eq!(solve_brute_force(&constraints, 9, 10), Ok(vec![0o21]));
+        // Ones that can't happen
+        let constraints = constraints_for_output(&machine, 3, &[1, 2]).unwrap();
+        assert!(solve(&constraints, 3, 10).is_empty());
+    }
+    #[test]
+    fn test_real_quine() {
+        let input = std::fs::read_to_string("input.txt").unwrap();
+        let machine = MiniPC::parse(&input).unwrap();
+        let constraints = constraints_for_output(&machine, 48, &machine.instructions).unwrap();
+        assert_eq!(solve(&constraints, 48, 1), [107416870455451]);
+    }
+}
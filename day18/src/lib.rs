@@ -0,0 +1,124 @@
+use shared::{pathfinding, Answer, Grid, ParseError, Solution};
+
+shared::register_day!(18, Day18);
+
+pub struct Day18;
+impl Solution for Day18 {
+    type Input = Vec<(usize, usize)>; // (col,row) of each byte, in the order they fall
+    type Config = Day18Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|line| {
+                let (a, b) = line
+                    .split_once(',')
+                    .ok_or_else(|| ParseError::new(format!("Invalid byte position `{line}`")))?;
+                Ok((a.parse()?, b.parse()?))
+            })
+            .collect()
+    }
+    fn part_a(input: &Self::Input, config: &Day18Config) -> Answer {
+        part_a(input, config.grid_size, config.steps).into()
+    }
+    fn part_b(input: &Self::Input, config: &Day18Config) -> Answer {
+        let (col, row) = part_b(input, config.grid_size);
+        Answer::Coordinate(col as i64, row as i64)
+    }
+}
+pub struct Day18Config {
+    pub grid_size: usize,
+    pub steps: usize, // Bytes that have fallen before part A's walk
+}
+impl Default for Day18Config {
+    fn default() -> Self {
+        Self {
+            grid_size: 71,
+            steps: 1024,
+        }
+    }
+}
+struct MemorySpace {
+    grid: Grid<bool>, //Is corrupt, default false
+    instructions: Vec<(usize, usize)>,
+    step: usize,
+}
+impl MemorySpace {
+    pub fn new(rows: usize, cols: usize, instructions: &[(usize, usize)]) -> Self {
+        //Instructions are a list of ( col,row) pairs
+        Self {
+            grid: Grid::filled_with(false, rows, cols),
+            instructions: instructions.to_vec(),
+            step: 0,
+        }
+    }
+    fn get_last_instruction(&self) -> (usize, usize) {
+        self.instructions[self.step - 1]
+    }
+    fn simulate_until(&mut self, steps: usize) {
+        while self.step < steps {
+            self.next_step();
+        }
+    }
+    fn next_step(&mut self) {
+        self.grid[self.instructions[self.step]] = true;
+        self.step += 1;
+    }
+    fn find_steps_to_exit(&self) -> Option<i64> {
+        let exit = (self.grid.num_rows() - 1, self.grid.num_columns() - 1);
+        // Walk onto any neighbour that isn't corrupt
+        pathfinding::bfs_distance(
+            (0, 0),
+            |&pos| self.grid.neighbours4(pos).filter(|&next| !self.grid[next]),
+            |&pos| pos == exit,
+        )
+        .map(|steps| steps as i64)
+    }
+}
+
+fn part_a(instructions: &[(usize, usize)], grid_size: usize, steps: usize) -> i64 {
+    let mut memory = MemorySpace::new(grid_size, grid_size, instructions);
+    memory.simulate_until(steps);
+    memory.find_steps_to_exit().unwrap()
+}
+fn part_b(instructions: &[(usize, usize)], grid_size: usize) -> (usize, usize) {
+    //TODO, could binary search this, but it takes sub 1 second so who cares
+    let mut memory = MemorySpace::new(grid_size, grid_size, instructions);
+    while memory.find_steps_to_exit().is_some() {
+        memory.next_step();
+    }
+    memory.get_last_instruction()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    const DEMO: Day18Config = Day18Config {
+        grid_size: 7,
+        steps: 12,
+    };
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day18::solve_file_with("test.txt", Part::A, &DEMO);
+        assert_eq!(results, 22);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day18::solve_file("input.txt", Part::A);
+        assert_eq!(results, 432);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day18::solve_file_with("test.txt", Part::B, &DEMO);
+        assert_eq!(results, "6,1");
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day18::solve_file("input.txt", Part::B);
+        assert_eq!(results, "56,27");
+    }
+}
@@ -1,30 +1,37 @@
 use std::collections::{HashMap, VecDeque};
 
 use rayon::prelude::*;
+use shared::{Answer, ParseError, Solution};
 
-fn main() {
-    let t_a = std::thread::spawn(|| {
-        println!("PART A: {}", part_a("input.txt"));
-    });
-    let t_b = std::thread::spawn(|| {
-        println!("PART B: {}", part_b("input.txt"));
-    });
-    t_a.join().unwrap();
-    t_b.join().unwrap();
+shared::register_day!(19, Day19);
+
+pub struct Day19;
+impl Solution for Day19 {
+    type Input = TowelSet;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        TowelSet::parse(input)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
 }
-struct TowelSet {
+pub struct TowelSet {
     available_patterns: Vec<String>,
     designs: Vec<String>,
 }
 impl TowelSet {
-    pub fn from_file(path: &str) -> Self {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
         //Read first line as CSV to find the combinations
-        let file = std::fs::read_to_string(path).unwrap();
-        let mut lines = file.lines();
+        let mut lines = input.lines();
 
         let mut available_patterns: Vec<String> = lines
             .next()
-            .unwrap()
+            .ok_or_else(|| ParseError::new("Missing towel patterns"))?
             .split(',')
             .map(|s| s.trim().to_string())
             .collect();
@@ -36,10 +43,10 @@ impl TowelSet {
         // All remaining lines are the requested patterns
         let designs: Vec<String> = lines.map(|s| s.trim().to_string()).collect();
 
-        Self {
+        Ok(Self {
             available_patterns,
             designs,
-        }
+        })
     }
     fn count_solutions_to_pattern<'a>(
         &self,
@@ -91,8 +98,7 @@ impl TowelSet {
         false
     }
 }
-fn part_a(path: &str) -> i64 {
-    let towel_set = TowelSet::from_file(path);
+fn part_a(towel_set: &TowelSet) -> i64 {
     let pool = rayon::ThreadPoolBuilder::new()
         .num_threads(8)
         .build()
@@ -109,8 +115,7 @@ fn part_a(path: &str) -> i64 {
             .sum()
     })
 }
-fn part_b(path: &str) -> i64 {
-    let towel_set = TowelSet::from_file(path);
+fn part_b(towel_set: &TowelSet) -> i64 {
     let pool = rayon::ThreadPoolBuilder::new()
         .num_threads(8)
         .build()
@@ -129,25 +134,26 @@ fn part_b(path: &str) -> i64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared::Part;
 
     #[test]
     fn test_part_a_demo() {
-        let results = part_a("test.txt");
+        let results = Day19::solve_file("test.txt", Part::A);
         assert_eq!(results, 6);
     }
     #[test]
     fn test_part_a_real() {
-        let results = part_a("input.txt");
+        let results = Day19::solve_file("input.txt", Part::A);
         assert_eq!(results, 216);
     }
     #[test]
     fn test_part_b_demo() {
-        let results = part_b("test.txt");
+        let results = Day19::solve_file("test.txt", Part::B);
         assert_eq!(results, 16);
     }
     #[test]
     fn test_part_b_real() {
-        let results = part_b("input.txt");
+        let results = Day19::solve_file("input.txt", Part::B);
         assert_eq!(results, 603191454138773);
     }
 }
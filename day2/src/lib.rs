@@ -1,14 +1,25 @@
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
-use shared::read_whitespace_separated_numbers_by_row;
+use shared::{parse_whitespace_separated_numbers_by_row, Answer, ParseError, Solution};
 
-fn main() {
-    println!("PART A: {}", part_a("input.txt"));
-    println!("PART B: {}", part_b("input.txt"));
-}
+shared::register_day!(2, Day2);
 
-fn part_a(path: &str) -> i64 {
-    let input = read_whitespace_separated_numbers_by_row(path).unwrap();
+pub struct Day2;
+impl Solution for Day2 {
+    type Input = Vec<Vec<i64>>;
+    type Config = ();
 
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_whitespace_separated_numbers_by_row(input)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+}
+
+fn part_a(input: &[Vec<i64>]) -> i64 {
     input
         .par_iter()
         .map(|row: &Vec<i64>| match is_row_safe(row) {
@@ -17,9 +28,7 @@ fn part_a(path: &str) -> i64 {
         })
         .sum()
 }
-fn part_b(path: &str) -> i64 {
-    let input = read_whitespace_separated_numbers_by_row(path).unwrap();
-
+fn part_b(input: &[Vec<i64>]) -> i64 {
     input
         .par_iter()
         .map(|row: &Vec<i64>| {
@@ -74,25 +83,26 @@ fn omit_one_entry(data: &[i64], index_removed: usize) -> Vec<i64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared::Part;
 
     #[test]
     fn test_part_a() {
-        let results = part_a("test.txt");
+        let results = Day2::solve_file("test.txt", Part::A);
         assert_eq!(results, 2);
     }
     #[test]
     fn test_part_a_real() {
-        let results = part_a("input.txt");
+        let results = Day2::solve_file("input.txt", Part::A);
         assert_eq!(results, 463);
     }
     #[test]
     fn test_part_b() {
-        let results = part_b("test.txt");
+        let results = Day2::solve_file("test.txt", Part::B);
         assert_eq!(results, 4);
     }
     #[test]
     fn test_part_b_real() {
-        let results = part_b("input.txt");
+        let results = Day2::solve_file("input.txt", Part::B);
         assert_eq!(results, 514);
     }
 }
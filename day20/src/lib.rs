@@ -0,0 +1,144 @@
+use itertools::Itertools;
+use shared::{parse_grid, pathfinding, Answer, Grid, ParseError, Solution};
+
+shared::register_day!(20, Day20);
+
+pub struct Day20;
+impl Solution for Day20 {
+    type Input = Map;
+    type Config = Day20Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::parse(input)
+    }
+    fn part_a(input: &Self::Input, config: &Day20Config) -> Answer {
+        input
+            .count_solutions_with_skip_count(2, config.target_saving)
+            .into()
+    }
+    fn part_b(input: &Self::Input, config: &Day20Config) -> Answer {
+        input
+            .count_solutions_with_skip_count(20, config.target_saving)
+            .into()
+    }
+}
+pub struct Day20Config {
+    pub target_saving: u32, // Only count cheats that save at least this many picoseconds
+}
+impl Default for Day20Config {
+    fn default() -> Self {
+        Self { target_saving: 100 }
+    }
+}
+
+pub struct Map {
+    grid: Grid<u8>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+impl Map {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = parse_grid(input)?;
+        let start_position = grid
+            .find(&b'S')
+            .ok_or_else(|| ParseError::new("Missing start `S` in map"))?;
+        let end_position = grid
+            .find(&b'E')
+            .ok_or_else(|| ParseError::new("Missing end `E` in map"))?;
+        Ok(Self {
+            grid,
+            start: start_position,
+            end: end_position,
+        })
+    }
+
+    fn count_solutions_with_skip_count(
+        &self,
+        maximum_skip_distance_inclusive: i64,
+        target_saving: u32,
+    ) -> i64 {
+        let (sc, ec) = (
+            self.compute_cost_to_exit(self.start),
+            self.compute_cost_to_exit(self.end),
+        );
+        // Cost from Start -> End
+        // Cost from End -> Start
+
+        let base_cost = sc[self.end]; // The fastest time to the end with no mods
+
+        let mut out = 0;
+
+        for (pos, tile) in self.grid.enumerate() {
+            // This tile is a wall, or it has no path to the exit
+            if *tile == b'#' || sc[pos] == u32::MAX {
+                continue;
+            }
+
+            for (x, y) in (-maximum_skip_distance_inclusive..=maximum_skip_distance_inclusive)
+                .cartesian_product(
+                    -maximum_skip_distance_inclusive..=maximum_skip_distance_inclusive,
+                )
+            {
+                let dist = x.abs() + y.abs();
+
+                if dist > maximum_skip_distance_inclusive {
+                    continue;
+                }
+                //If result pos is out of the grid, or is a wall, or has no path to the exit
+                let Some(result_pos) = self.grid.offset(pos, (x, y)) else {
+                    continue;
+                };
+                if self.grid[result_pos] == b'#' || ec[result_pos] == u32::MAX {
+                    continue;
+                }
+                // println!("Sc {} ec {} dist {}", sc[pos], ec[result_pos], dist);
+                let cost = sc[pos] + ec[result_pos] + dist as u32;
+                out += ((cost + target_saving) <= base_cost) as u32;
+            }
+        }
+
+        out as i64
+    }
+    fn compute_cost_to_exit(&self, start_position: (usize, usize)) -> Grid<u32> {
+        let mut costs = Grid::filled_with(u32::MAX, self.grid.num_rows(), self.grid.num_columns());
+        // Walk from the start onto all possible tiles
+        let distances = pathfinding::bfs(start_position, |&pos| {
+            self.grid
+                .neighbours4(pos)
+                .filter(|&next| matches!(self.grid[next], b'.' | b'E'))
+        });
+        for (pos, dist) in distances {
+            costs[pos] = dist as u32;
+        }
+        costs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    const DEMO: Day20Config = Day20Config { target_saving: 38 };
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day20::solve_file_with("test.txt", Part::A, &DEMO);
+        assert_eq!(results, 3);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day20::solve_file("input.txt", Part::A);
+        assert_eq!(results, 1372);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day20::solve_file_with("test.txt", Part::B, &DEMO);
+        assert_eq!(results, 644);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day20::solve_file("input.txt", Part::B);
+        assert_eq!(results, 979014);
+    }
+}
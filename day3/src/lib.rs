@@ -0,0 +1,64 @@
+use shared::{Answer, ParseError, Solution};
+
+mod tokenizer;
+pub use tokenizer::*;
+
+shared::register_day!(3, Day3);
+
+pub struct Day3;
+impl Solution for Day3 {
+    // Tokenized straight from the borrowed input, only the instructions found are kept
+    type Input = Vec<Token>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Tokenizer::new(input).collect())
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+}
+
+fn part_a(tokens: &[Token]) -> i64 {
+    tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Mul(a, b) => Some(a * b),
+            _ => None,
+        })
+        .sum()
+}
+fn part_b(tokens: &[Token]) -> i64 {
+    // Line breaks are just more garbage to the tokenizer, so there's no need to strip them
+    enabled(tokens.iter().copied()).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day3::solve_file("test.txt", Part::A);
+        assert_eq!(results, 161);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day3::solve_file("input.txt", Part::A);
+        assert_eq!(results, 190604937);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day3::solve_file("test2.txt", Part::B);
+        assert_eq!(results, 48);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day3::solve_file("input.txt", Part::B);
+        assert_eq!(results, 82857512);
+    }
+}
@@ -0,0 +1,234 @@
+// Finds the instructions in corrupted memory in one pass, a byte at a time, without copying any of it
+//
+// Each byte moves a small state machine along `mul(a,b)`, `do()` or `don't()`. Anything that doesn't
+// fit resets it, and that byte gets another look as the possible start of the next instruction
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    Mul(i64, i64),
+    Do,
+    Dont,
+}
+
+// How far through an instruction we are, named by what's been matched so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Start,
+    M,
+    Mu,
+    Mul,
+    Left { value: i64, digits: u8 },
+    Right { left: i64, value: i64, digits: u8 },
+    D,
+    Do,
+    DoOpen,
+    Don,
+    DonApostrophe,
+    Dont,
+    DontOpen,
+}
+
+// Numbers in a mul are 1 to 3 digits
+const MAX_DIGITS: u8 = 3;
+
+pub struct Tokenizer<'a> {
+    memory: &'a [u8],
+    position: usize,
+    token_start: usize,
+}
+impl<'a> Tokenizer<'a> {
+    pub fn new(memory: &'a str) -> Self {
+        Self {
+            memory: memory.as_bytes(),
+            position: 0,
+            token_start: 0,
+        }
+    }
+    // Byte offset of the last token returned
+    pub fn token_start(&self) -> usize {
+        self.token_start
+    }
+}
+impl Iterator for Tokenizer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        let mut state = State::Start;
+        while let Some(&byte) = self.memory.get(self.position) {
+            self.position += 1;
+            let (next, token) = step(state, byte);
+            state = match next {
+                // Didn't fit, but it might start something new
+                None => step(State::Start, byte).0.unwrap_or(State::Start),
+                Some(next) => next,
+            };
+            if state == State::M || state == State::D {
+                self.token_start = self.position - 1;
+            }
+            if token.is_some() {
+                return token;
+            }
+        }
+        None
+    }
+}
+
+// Where the byte takes the state, None if it doesn't fit, and the token if it finishes one
+fn step(state: State, byte: u8) -> (Option<State>, Option<Token>) {
+    let next = match (state, byte) {
+        (State::Start, b'm') => State::M,
+        (State::Start, b'd') => State::D,
+        (State::Start, _) => State::Start,
+
+        (State::M, b'u') => State::Mu,
+        (State::Mu, b'l') => State::Mul,
+        (State::Mul, b'(') => State::Left {
+            value: 0,
+            digits: 0,
+        },
+        (State::Left { value, digits }, b'0'..=b'9') if digits < MAX_DIGITS => State::Left {
+            value: value * 10 + (byte - b'0') as i64,
+            digits: digits + 1,
+        },
+        (State::Left { value, digits }, b',') if digits > 0 => State::Right {
+            left: value,
+            value: 0,
+            digits: 0,
+        },
+        (
+            State::Right {
+                left,
+                value,
+                digits,
+            },
+            b'0'..=b'9',
+        ) if digits < MAX_DIGITS => State::Right {
+            left,
+            value: value * 10 + (byte - b'0') as i64,
+            digits: digits + 1,
+        },
+        (
+            State::Right {
+                left,
+                value,
+                digits,
+            },
+            b')',
+        ) if digits > 0 => return (Some(State::Start), Some(Token::Mul(left, value))),
+
+        (State::D, b'o') => State::Do,
+        (State::Do, b'(') => State::DoOpen,
+        (State::DoOpen, b')') => return (Some(State::Start), Some(Token::Do)),
+        (State::Do, b'n') => State::Don,
+        (State::Don, b'\'') => State::DonApostrophe,
+        (State::DonApostrophe, b't') => State::Dont,
+        (State::Dont, b'(') => State::DontOpen,
+        (State::DontOpen, b')') => return (Some(State::Start), Some(Token::Dont)),
+        _ => return (None, None),
+    };
+    (Some(next), None)
+}
+
+// The mul instructions that count, skipping any after a don't() until the next do()
+pub struct Enabled<I> {
+    tokens: I,
+    enabled: bool,
+}
+impl<I: Iterator<Item = Token>> Iterator for Enabled<I> {
+    type Item = (i64, i64);
+
+    fn next(&mut self) -> Option<(i64, i64)> {
+        for token in self.tokens.by_ref() {
+            match token {
+                Token::Do => self.enabled = true,
+                Token::Dont => self.enabled = false,
+                Token::Mul(a, b) if self.enabled => return Some((a, b)),
+                Token::Mul(..) => {}
+            }
+        }
+        None
+    }
+}
+pub fn enabled<I: IntoIterator<Item = Token>>(tokens: I) -> Enabled<I::IntoIter> {
+    Enabled {
+        tokens: tokens.into_iter(),
+        enabled: true,
+    }
+}
+
+// Byte ranges of the memory where muls count, each from a do() (or the start) up to the next don't()
+pub fn enabled_regions(memory: &str) -> Vec<Range<usize>> {
+    let mut regions = Vec::new();
+    let mut enabled_from = Some(0);
+    let mut tokens = Tokenizer::new(memory);
+    while let Some(token) = tokens.next() {
+        match (token, enabled_from) {
+            (Token::Dont, Some(start)) => {
+                regions.push(start..tokens.token_start());
+                enabled_from = None;
+            }
+            (Token::Do, None) => enabled_from = Some(tokens.token_start()),
+            _ => {}
+        }
+    }
+    if let Some(start) = enabled_from {
+        regions.push(start..memory.len());
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(memory: &str) -> Vec<Token> {
+        Tokenizer::new(memory).collect()
+    }
+    #[test]
+    fn test_tokens() {
+        let memory = std::fs::read_to_string("test2.txt").unwrap();
+        assert_eq!(
+            tokens(&memory),
+            [
+                Token::Mul(2, 4),
+                Token::Dont,
+                Token::Mul(5, 5),
+                Token::Mul(11, 8),
+                Token::Do,
+                Token::Mul(8, 5)
+            ]
+        );
+        // Restarts partway through something that didn't fit
+        assert_eq!(tokens("mmul(1,2)"), [Token::Mul(1, 2)]);
+        assert_eq!(tokens("mul(1,mul(3,4)"), [Token::Mul(3, 4)]);
+        assert_eq!(tokens("dodon't()do()"), [Token::Dont, Token::Do]);
+        // Garbage
+        assert_eq!(tokens("mul(1234,5)mul(,5)mul(5,)mul (1,2)mul(1,2"), []);
+        assert_eq!(tokens("don't(do(mul[1,2]dont()"), []);
+        assert_eq!(tokens("mül(1,2)mul(999,0)"), [Token::Mul(999, 0)]);
+    }
+    #[test]
+    fn test_enabled() {
+        let memory = std::fs::read_to_string("test2.txt").unwrap();
+        let products: Vec<(i64, i64)> = enabled(Tokenizer::new(&memory)).collect();
+        assert_eq!(products, [(2, 4), (8, 5)]);
+
+        let regions = enabled_regions(&memory);
+        assert_eq!(regions.len(), 2);
+        assert!(memory[regions[0].clone()].ends_with("!^"));
+        assert!(memory[regions[1].clone()].starts_with("do()?"));
+        assert_eq!(enabled_regions("xdon't()ydo()z"), [0..1, 9..14]);
+        assert_eq!(enabled_regions("don't()do()"), [0..0, 7..11]);
+    }
+    #[test]
+    fn test_large_input() {
+        // A few megabytes of the demo over and over, with line breaks like the real input
+        let memory = std::fs::read_to_string("test2.txt").unwrap();
+        let large = format!("{}\n", memory.trim_end()).repeat(50_000);
+        assert!(large.len() > 3_000_000);
+        let total: i64 = enabled(Tokenizer::new(&large)).map(|(a, b)| a * b).sum();
+        // Each copy ends on a do(), so the next starts enabled just like the first
+        assert_eq!(total, 48 * 50_000);
+    }
+}
@@ -0,0 +1,160 @@
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use shared::{parse_grid, Answer, Direction, Grid, ParseError, Solution, ALL_DIRECTIONS_8};
+
+shared::register_day!(4, Day4);
+
+pub struct Day4;
+impl Solution for Day4 {
+    type Input = Grid<u8>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+}
+
+fn part_a(grid: &Grid<u8>) -> i64 {
+    // Reading along every direction covers the word being backwards too
+    let search_word = "XMAS";
+
+    (0..grid.num_rows())
+        .into_par_iter()
+        .map(|row| {
+            (0..grid.num_columns())
+                .into_par_iter()
+                .map(|col| {
+                    ALL_DIRECTIONS_8
+                        .into_iter()
+                        .filter(|&dir| search_at(grid, search_word, dir, row, col))
+                        .count() as i64
+                })
+                .sum::<i64>()
+        })
+        .sum::<i64>()
+}
+fn part_b(grid: &Grid<u8>) -> i64 {
+    let grid_search = [
+        // M_M
+        // _A_
+        // S_S
+        [
+            (0, 0, b'M'),
+            (0, 2, b'M'), //
+            (1, 1, b'A'), //
+            (2, 0, b'S'),
+            (2, 2, b'S'), //
+        ],
+        // S_M
+        // _A_
+        // S_M
+        [
+            (0, 0, b'S'),
+            (0, 2, b'M'), //
+            (1, 1, b'A'), //
+            (2, 0, b'S'),
+            (2, 2, b'M'), //
+        ],
+        // S_S
+        // _A_
+        // M_M
+        [
+            (0, 0, b'S'),
+            (0, 2, b'S'), //
+            (1, 1, b'A'), //
+            (2, 0, b'M'),
+            (2, 2, b'M'), //
+        ],
+        // M_S
+        // _A_
+        // M_S
+        [
+            (0, 0, b'M'),
+            (0, 2, b'S'), //
+            (1, 1, b'A'), //
+            (2, 0, b'M'),
+            (2, 2, b'S'), //
+        ],
+    ];
+
+    (0..grid.num_rows())
+        .into_par_iter()
+        .map(|row| {
+            (0..grid.num_columns())
+                .into_par_iter()
+                .map(|col| {
+                    grid_search
+                        .into_iter()
+                        .map(|pattern| {
+                            if match_grid_pattern(grid, row, col, &pattern) {
+                                1
+                            } else {
+                                0
+                            }
+                        })
+                        .sum::<i64>()
+                })
+                .sum::<i64>()
+        })
+        .sum::<i64>()
+}
+fn match_grid_pattern(
+    grid: &Grid<u8>,
+    base_row: usize,
+    base_col: usize,
+    pattern: &[(usize, usize, u8)],
+) -> bool {
+    for (check_row, check_col, check_val) in pattern {
+        let row = base_row + check_row;
+        let col = base_col + check_col;
+        let grid_val = grid.get((row, col));
+        match grid_val {
+            Some(val) => {
+                if *val != *check_val {
+                    return false;
+                }
+            }
+            None => return false,
+        }
+    }
+    true
+}
+fn search_at(grid: &Grid<u8>, word: &str, direction: Direction, row: usize, col: usize) -> bool {
+    let (dr, dc) = direction.delta();
+    word.bytes().enumerate().all(|(i, letter)| {
+        let i = i as i64;
+        grid.get_signed(row as i64 + dr * i, col as i64 + dc * i) == Some(&letter)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day4::solve_file("test.txt", Part::A);
+        assert_eq!(results, 18);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day4::solve_file("input.txt", Part::A);
+        assert_eq!(results, 2639);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day4::solve_file("test.txt", Part::B);
+        assert_eq!(results, 9);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day4::solve_file("input.txt", Part::B);
+        assert_eq!(results, 2005);
+    }
+}
@@ -0,0 +1,199 @@
+// The ordering rules as a graph, with an edge from each page to the pages that have to come after it
+// The full rule set has cycles in it, so only the rules between pages in one update get used at a time
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet},
+    fmt::Display,
+};
+
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrderRule {
+    pub first: i64,
+    pub second: i64,
+}
+impl Display for OrderRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.first, self.second)
+    }
+}
+
+// The rules between an update's pages went round in a loop, listed in the order they chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    pub rules: Vec<OrderRule>,
+}
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Rules form a cycle: {}", self.rules.iter().format(", "))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PrecedenceGraph {
+    rules: Vec<OrderRule>,
+    successors: HashMap<i64, HashSet<i64>>,
+}
+impl PrecedenceGraph {
+    pub fn new(rules: impl IntoIterator<Item = OrderRule>) -> Self {
+        let mut graph = Self::default();
+        for rule in rules {
+            graph.add_rule(rule);
+        }
+        graph
+    }
+    pub fn add_rule(&mut self, rule: OrderRule) {
+        if self
+            .successors
+            .entry(rule.first)
+            .or_default()
+            .insert(rule.second)
+        {
+            self.rules.push(rule);
+        }
+    }
+    pub fn rules(&self) -> &[OrderRule] {
+        &self.rules
+    }
+    pub fn must_precede(&self, first: i64, second: i64) -> bool {
+        self.successors
+            .get(&first)
+            .is_some_and(|after| after.contains(&second))
+    }
+    // Only direct rules count, pages with no rule between them compare equal
+    pub fn compare(&self, a: i64, b: i64) -> Ordering {
+        if self.must_precede(a, b) {
+            Ordering::Less
+        } else if self.must_precede(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+    pub fn is_ordered(&self, seq: &[i64]) -> bool {
+        seq.iter()
+            .tuple_combinations()
+            .all(|(&a, &b)| !self.must_precede(b, a))
+    }
+
+    // Kahn's algorithm over the rules between the sequence's pages
+    // Pages that are free to go next keep their original order, so sorted sequences come back as they were
+    pub fn sort(&self, seq: &[i64]) -> Result<Vec<i64>, CycleError> {
+        let edges = self.induced_edges(seq);
+        match topological_order(&edges) {
+            Ok(order) => Ok(order.into_iter().map(|i| seq[i]).collect()),
+            Err(incoming) => Err(self.find_cycle(seq, &edges, &incoming)),
+        }
+    }
+    // Edges by position in the sequence, so repeated pages don't get tangled up
+    pub(crate) fn induced_edges(&self, seq: &[i64]) -> Vec<Vec<usize>> {
+        seq.iter()
+            .map(|&page| {
+                (0..seq.len())
+                    .filter(|&j| self.must_precede(page, seq[j]))
+                    .collect()
+            })
+            .collect()
+    }
+    // Every page Kahn's couldn't place is on a cycle or after one, walking backwards through them
+    // from any of them has to end up going round a cycle
+    fn find_cycle(&self, seq: &[i64], edges: &[Vec<usize>], incoming: &[usize]) -> CycleError {
+        let stuck = |i: usize| incoming[i] > 0;
+        let mut predecessor = vec![None; seq.len()];
+        for (from, nexts) in edges.iter().enumerate().filter(|&(i, _)| stuck(i)) {
+            for &next in nexts {
+                predecessor[next].get_or_insert(from);
+            }
+        }
+        let mut visited = vec![false; seq.len()];
+        let mut current = (0..seq.len()).find(|&i| stuck(i)).unwrap();
+        while !visited[current] {
+            visited[current] = true;
+            current = predecessor[current].unwrap();
+        }
+        // Current is on the cycle, go round it once collecting the rules
+        let mut rules = Vec::new();
+        let start = current;
+        loop {
+            let before = predecessor[current].unwrap();
+            rules.push(OrderRule {
+                first: seq[before],
+                second: seq[current],
+            });
+            current = before;
+            if current == start {
+                break;
+            }
+        }
+        rules.reverse();
+        CycleError { rules }
+    }
+}
+
+// Positions in dependency order, or what's left of the incoming counts if there's a cycle
+pub(crate) fn topological_order(edges: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let mut incoming = vec![0; edges.len()];
+    for &next in edges.iter().flatten() {
+        incoming[next] += 1;
+    }
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..edges.len())
+        .filter(|&i| incoming[i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(edges.len());
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(i);
+        for &next in &edges[i] {
+            incoming[next] -= 1;
+            if incoming[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+    if order.len() < edges.len() {
+        return Err(incoming);
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(rules: &[(i64, i64)]) -> PrecedenceGraph {
+        PrecedenceGraph::new(
+            rules
+                .iter()
+                .map(|&(first, second)| OrderRule { first, second }),
+        )
+    }
+    #[test]
+    fn test_sort() {
+        let graph = graph(&[(1, 2), (2, 3), (1, 3), (5, 4)]);
+        assert_eq!(graph.sort(&[3, 2, 1]), Ok(vec![1, 2, 3]));
+        // 4 and 5 aren't tied to the rest so stay in front
+        assert_eq!(graph.sort(&[4, 3, 5, 1]), Ok(vec![5, 4, 1, 3]));
+        assert!(graph.is_ordered(&[1, 5, 2, 4, 3]));
+        assert!(!graph.is_ordered(&[2, 1]));
+        assert_eq!(graph.compare(3, 1), Ordering::Greater);
+        assert_eq!(graph.compare(3, 4), Ordering::Equal);
+    }
+    #[test]
+    fn test_cycles() {
+        let graph = graph(&[(1, 2), (2, 3), (3, 1), (3, 4), (0, 1)]);
+        // The loop is only a problem once all three pages are in the same update
+        assert_eq!(graph.sort(&[2, 3]), Ok(vec![2, 3]));
+        let error = graph.sort(&[4, 3, 0, 2, 1]).unwrap_err();
+        assert_eq!(error.rules.len(), 3);
+        assert!(error.rules.contains(&OrderRule {
+            first: 3,
+            second: 1
+        }));
+        // Each rule picks up where the one before it left off
+        for (a, b) in error.rules.iter().circular_tuple_windows() {
+            assert_eq!(a.second, b.first);
+        }
+        assert!(error.to_string().starts_with("Rules form a cycle: "));
+    }
+}
@@ -0,0 +1,135 @@
+use rayon::prelude::*;
+use shared::{Answer, ParseError, Solution};
+
+mod graph;
+mod repair;
+pub use graph::*;
+pub use repair::*;
+
+shared::register_day!(5, Day5);
+
+pub struct Day5;
+impl Solution for Day5 {
+    type Input = Input;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Input::parse(input)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        match part_b(input) {
+            Ok(total) => total.into(),
+            Err(e) => e.to_string().into(),
+        }
+    }
+}
+pub struct Input {
+    graph: PrecedenceGraph,
+    sequences: Vec<Vec<i64>>,
+}
+impl Input {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut split = false;
+        let mut sequences = Vec::new();
+        let mut graph = PrecedenceGraph::default();
+
+        for mut line in input.lines() {
+            line = line.trim();
+            if line.is_empty() {
+                split = true;
+                continue;
+            }
+            if split {
+                let mut seq: Vec<i64> = Vec::new();
+                for num in line.split(",") {
+                    seq.push(num.parse()?);
+                }
+                sequences.push(seq);
+            } else {
+                let (first, second) = line
+                    .split_once("|")
+                    .ok_or_else(|| ParseError::new(format!("Invalid rule `{line}`")))?;
+                let first = first.parse()?;
+                let second = second.parse()?;
+                graph.add_rule(OrderRule { first, second });
+            }
+        }
+
+        Ok(Self { graph, sequences })
+    }
+    pub fn graph(&self) -> &PrecedenceGraph {
+        &self.graph
+    }
+    pub fn sequences(&self) -> &[Vec<i64>] {
+        &self.sequences
+    }
+}
+// Of the two middle pages in an even length update, the first one
+fn sequence_middle(seq: &[i64]) -> i64 {
+    seq[(seq.len() - 1) / 2]
+}
+
+fn part_a(input: &Input) -> i64 {
+    // println!(
+    //     "Loaded {} fules, and {} sequences",
+    //     input.graph.rules().len(),
+    //     input.sequences.len()
+    // );
+
+    input
+        .sequences
+        .par_iter()
+        .filter(|seq| input.graph.is_ordered(seq))
+        .map(|x| sequence_middle(x))
+        .sum::<i64>()
+}
+fn part_b(input: &Input) -> Result<i64, CycleError> {
+    input
+        .sequences
+        .par_iter()
+        .filter(|seq| !input.graph.is_ordered(seq))
+        .map(|seq| Ok(sequence_middle(&input.graph.sort(seq)?)))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day5::solve_file("test.txt", Part::A);
+        assert_eq!(results, 143);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day5::solve_file("input.txt", Part::A);
+        assert_eq!(results, 7024);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day5::solve_file("test.txt", Part::B);
+        assert_eq!(results, 123);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day5::solve_file("input.txt", Part::B);
+        assert_eq!(results, 4151);
+    }
+    #[test]
+    fn test_sequence_middle() {
+        assert_eq!(sequence_middle(&[1, 2, 3]), 2);
+        assert_eq!(sequence_middle(&[1, 2, 3, 4]), 2);
+        assert_eq!(sequence_middle(&[7]), 7);
+    }
+    #[test]
+    fn test_part_b_cycle() {
+        let input = Input::parse("1|2\n2|3\n3|1\n\n3,2,1").unwrap();
+        let error = part_b(&input).unwrap_err();
+        assert_eq!(error.rules.len(), 3);
+    }
+}
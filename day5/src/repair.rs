@@ -0,0 +1,307 @@
+// Explains what's wrong with an update, and the fewest page moves that put it right
+//
+// Pages that don't get moved keep their order, so they can't include two pages the rules put the
+// other way round, even through pages in between. Those clashing pairs form a partial order, and the
+// biggest set of pages with no clashes is its largest antichain, which Dilworth's theorem gives us
+// from a bipartite matching
+use std::fmt::Display;
+
+use crate::{topological_order, CycleError, OrderRule, PrecedenceGraph};
+
+// A rule broken by the update, with the page that should come first turning up after the other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub rule: OrderRule,
+    pub first_position: usize,
+    pub second_position: usize,
+}
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} broken, {} is at {} after {} at {}",
+            self.rule, self.rule.first, self.first_position, self.rule.second, self.second_position
+        )
+    }
+}
+
+// Take the page out of `from` and put it back in so it ends up at `to`, applied one after another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepairMove {
+    pub page: i64,
+    pub from: usize,
+    pub to: usize,
+}
+impl Display for RepairMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.page, self.from, self.to)
+    }
+}
+impl RepairMove {
+    pub fn apply(&self, seq: &mut Vec<i64>) {
+        let page = seq.remove(self.from);
+        seq.insert(self.to, page);
+    }
+}
+
+impl PrecedenceGraph {
+    // Ordered by where the broken rule's second page sits
+    pub fn violations(&self, seq: &[i64]) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for (second_position, &second) in seq.iter().enumerate() {
+            for (first_position, &first) in seq.iter().enumerate().skip(second_position + 1) {
+                if self.must_precede(first, second) {
+                    violations.push(Violation {
+                        rule: OrderRule { first, second },
+                        first_position,
+                        second_position,
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    // As few moves as possible, empty if the update is already in order
+    pub fn repair_moves(&self, seq: &[i64]) -> Result<Vec<RepairMove>, CycleError> {
+        // Also catches the updates that can't be put in order at all
+        self.sort(seq)?;
+        let mut edges = self.induced_edges(seq);
+        let reach = reachability(&edges);
+        // Later page `i` clashes with earlier `j` when `i` has to end up in front of it
+        let clashes: Vec<Vec<usize>> = (0..seq.len())
+            .map(|i| (0..i).filter(|&j| reach[i][j]).collect())
+            .collect();
+        let kept = largest_antichain(&clashes);
+
+        // Chaining the kept pages together keeps them in order in the target
+        let kept_positions: Vec<usize> = (0..seq.len()).filter(|&i| kept[i]).collect();
+        for pair in kept_positions.windows(2) {
+            edges[pair[0]].push(pair[1]);
+        }
+        let target = topological_order(&edges).expect("Kept pages have no clashes");
+        let mut rank = vec![0; seq.len()];
+        for (r, &i) in target.iter().enumerate() {
+            rank[i] = r;
+        }
+
+        // Slot each moved page in just after the closest page before it that's already in place
+        let mut current: Vec<usize> = (0..seq.len()).collect();
+        let moved: Vec<usize> = target.iter().copied().filter(|&i| !kept[i]).collect();
+        let mut placed = kept;
+        let mut moves = Vec::new();
+        for i in moved {
+            let from = current.iter().position(|&c| c == i).unwrap();
+            current.remove(from);
+            let to = current
+                .iter()
+                .rposition(|&c| placed[c] && rank[c] < rank[i])
+                .map_or(0, |p| p + 1);
+            current.insert(to, i);
+            placed[i] = true;
+            if from != to {
+                moves.push(RepairMove {
+                    page: seq[i],
+                    from,
+                    to,
+                });
+            }
+        }
+        Ok(moves)
+    }
+}
+
+// Which positions can be reached from which, the induced graph is small so a search from each will do
+fn reachability(edges: &[Vec<usize>]) -> Vec<Vec<bool>> {
+    (0..edges.len())
+        .map(|start| {
+            let mut seen = vec![false; edges.len()];
+            let mut stack = edges[start].clone();
+            while let Some(i) = stack.pop() {
+                if !seen[i] {
+                    seen[i] = true;
+                    stack.extend(&edges[i]);
+                }
+            }
+            seen
+        })
+        .collect()
+}
+
+// `above[i]` lists everything `i` is bigger than in a transitive order, returns a biggest set with no
+// two comparable. The chains in a smallest chain cover come from a maximum matching of each element
+// to one below it, then König's theorem turns the matching into the antichain
+fn largest_antichain(above: &[Vec<usize>]) -> Vec<bool> {
+    let n = above.len();
+    let mut matched_below: Vec<Option<usize>> = vec![None; n];
+    let mut matched_above: Vec<Option<usize>> = vec![None; n];
+    for top in 0..n {
+        let mut visited = vec![false; n];
+        augment(
+            top,
+            above,
+            &mut visited,
+            &mut matched_below,
+            &mut matched_above,
+        );
+    }
+
+    // Alternating paths out of the unmatched tops
+    let mut top_reached = vec![false; n];
+    let mut bottom_reached = vec![false; n];
+    let mut stack: Vec<usize> = (0..n).filter(|&i| matched_below[i].is_none()).collect();
+    while let Some(top) = stack.pop() {
+        if top_reached[top] {
+            continue;
+        }
+        top_reached[top] = true;
+        for &bottom in &above[top] {
+            if !bottom_reached[bottom] {
+                bottom_reached[bottom] = true;
+                if let Some(next) = matched_above[bottom] {
+                    stack.push(next);
+                }
+            }
+        }
+    }
+    (0..n)
+        .map(|i| top_reached[i] && !bottom_reached[i])
+        .collect()
+}
+// Kuhn's augmenting path search
+fn augment(
+    top: usize,
+    above: &[Vec<usize>],
+    visited: &mut [bool],
+    matched_below: &mut [Option<usize>],
+    matched_above: &mut [Option<usize>],
+) -> bool {
+    for &bottom in &above[top] {
+        if visited[bottom] {
+            continue;
+        }
+        visited[bottom] = true;
+        let free = match matched_above[bottom] {
+            None => true,
+            Some(other) => augment(other, above, visited, matched_below, matched_above),
+        };
+        if free {
+            matched_below[top] = Some(bottom);
+            matched_above[bottom] = Some(top);
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demo() -> PrecedenceGraph {
+        let rules = std::fs::read_to_string("test.txt").unwrap();
+        PrecedenceGraph::new(rules.lines().map_while(|line| {
+            let (first, second) = line.split_once('|')?;
+            Some(OrderRule {
+                first: first.parse().unwrap(),
+                second: second.parse().unwrap(),
+            })
+        }))
+    }
+    fn repaired(graph: &PrecedenceGraph, seq: &[i64]) -> (usize, Vec<i64>) {
+        let moves = graph.repair_moves(seq).unwrap();
+        let mut fixed = seq.to_vec();
+        moves.iter().for_each(|m| m.apply(&mut fixed));
+        (moves.len(), fixed)
+    }
+    #[test]
+    fn test_violations() {
+        let graph = demo();
+        let violations = graph.violations(&[75, 97, 47, 61, 53]);
+        assert_eq!(
+            violations,
+            [Violation {
+                rule: OrderRule {
+                    first: 97,
+                    second: 75
+                },
+                first_position: 1,
+                second_position: 0
+            }]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "97|75 broken, 97 is at 1 after 75 at 0"
+        );
+        assert!(graph.violations(&[75, 47, 61, 53, 29]).is_empty());
+    }
+    #[test]
+    fn test_repair_moves() {
+        let graph = demo();
+        assert_eq!(
+            graph.repair_moves(&[75, 97, 47, 61, 53]),
+            Ok(vec![RepairMove {
+                page: 97,
+                from: 1,
+                to: 0
+            }])
+        );
+        assert_eq!(repaired(&graph, &[61, 13, 29]), (1, vec![61, 29, 13]));
+        // Fully backwards needs everything but one page moved
+        assert_eq!(
+            repaired(&graph, &[13, 29, 47, 75, 97]),
+            (4, vec![97, 75, 47, 29, 13])
+        );
+        assert_eq!(
+            repaired(&graph, &[75, 47, 61, 53, 29]),
+            (0, vec![75, 47, 61, 53, 29])
+        );
+    }
+    #[test]
+    fn test_repair_respects_pages_in_between() {
+        // 2 and 1 have no rule between them directly, but they can't both stay put as 3 has to go
+        // between them
+        let graph = PrecedenceGraph::new([
+            OrderRule {
+                first: 1,
+                second: 3,
+            },
+            OrderRule {
+                first: 3,
+                second: 2,
+            },
+        ]);
+        assert_eq!(repaired(&graph, &[2, 1, 3]), (1, vec![1, 3, 2]));
+    }
+    #[test]
+    fn test_repairs_are_minimal() {
+        // Compare against trying every set of pages to leave where they are
+        let graph = demo();
+        let pages = [97, 75, 47, 61, 53, 29, 13];
+        let mut seed = 5u64;
+        for _ in 0..50 {
+            let mut seq = pages.to_vec();
+            for i in (1..seq.len()).rev() {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                seq.swap(i, (seed >> 33) as usize % (i + 1));
+            }
+            let (moves, fixed) = repaired(&graph, &seq);
+            assert!(graph.is_ordered(&fixed), "{seq:?} became {fixed:?}");
+            let most_kept = (0u32..1 << seq.len())
+                .filter(|mask| {
+                    let kept: Vec<i64> = (0..seq.len())
+                        .filter(|i| mask & (1 << i) != 0)
+                        .map(|i| seq[i])
+                        .collect();
+                    // The demo rules are a total order, so kept pages just have to be in order
+                    graph.is_ordered(&kept)
+                })
+                .map(|mask| mask.count_ones() as usize)
+                .max()
+                .unwrap();
+            assert_eq!(moves, seq.len() - most_kept, "{seq:?}");
+        }
+    }
+}
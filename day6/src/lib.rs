@@ -0,0 +1,178 @@
+use hibitset::BitSet;
+use rayon::prelude::*;
+use shared::{
+    parse_grid,
+    viz::{Palette, Recorder, Rgb},
+    Answer, Direction, Grid, ParseError, Part, Solution,
+};
+
+shared::register_day!(6, Day6);
+
+pub struct Day6;
+impl Solution for Day6 {
+    type Input = Grid<u8>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+    fn visualize(input: &Self::Input, part: Part, _config: &()) -> Option<Recorder> {
+        // Only part A's walk is worth watching, part B is thousands of them
+        if part != Part::A {
+            return None;
+        }
+        let mut recorder = Recorder::new(Palette::new(&COLOURS)).every(8).scale(2);
+        find_guard(input).walk_matrix_count_steps(input.clone(), Some(&mut recorder));
+        Some(recorder)
+    }
+}
+// Floor, walls, visited cells and the guard
+const FLOOR: u8 = 0;
+const WALL: u8 = 1;
+const VISITED: u8 = 2;
+const GUARD: u8 = 3;
+const COLOURS: [Rgb; 4] = [
+    [0x10, 0x10, 0x18],
+    [0x80, 0x80, 0x80],
+    [0x30, 0x60, 0xC0],
+    [0xFF, 0x40, 0x40],
+];
+#[derive(Clone, Copy)]
+struct Guard {
+    current_row: i64,
+    current_col: i64,
+    current_direction: Direction,
+}
+impl Guard {
+    fn next_position(&self) -> (i64, i64) {
+        let (dr, dc) = self.current_direction.delta();
+        (self.current_row + dr, self.current_col + dc)
+    }
+    fn record(&self, grid: &Grid<u8>, recorder: &mut Recorder) {
+        let mut frame = grid.map(|&cell| match cell {
+            b'#' => WALL,
+            b'x' => VISITED,
+            _ => FLOOR,
+        });
+        frame[(self.current_row as usize, self.current_col as usize)] = GUARD;
+        recorder.record_grid(&frame, |&colour| colour);
+    }
+    pub fn walk_matrix_count_steps(
+        &mut self,
+        mut grid: Grid<u8>,
+        mut recorder: Option<&mut Recorder>,
+    ) -> i64 {
+        let mut steps = 1;
+        grid[(self.current_row as usize, self.current_col as usize)] = b'x';
+        loop {
+            if let Some(recorder) = recorder.as_deref_mut() {
+                self.record(&grid, recorder);
+            }
+            let (row, col) = self.next_position();
+            // If out of bounds, we are done
+            let Some(&next) = grid.get_signed(row, col) else {
+                break;
+            };
+            if next == b'#' {
+                self.current_direction = self.current_direction.turn_right();
+            } else {
+                self.current_row = row;
+                self.current_col = col;
+                if next != b'x' {
+                    steps += 1;
+                    grid[(row as usize, col as usize)] = b'x';
+                }
+            }
+        }
+        steps
+    }
+    pub fn walk_matrix_does_loop(&mut self, grid: &Grid<u8>) -> bool {
+        // Only goes up to like 130 rows, ln2(130) = 7,So allocate 8 bits for row, 8 bits for column, 3 bits for direction
+        //
+        let max_value = 1 << (8 + 8 + 3);
+        let mut position_back_buffer: BitSet = BitSet::with_capacity(max_value as u32);
+
+        loop {
+            let (row, col) = self.next_position();
+            // If out of bounds, we are done
+            let Some(&next) = grid.get_signed(row, col) else {
+                return false; // Doesn't loop as we hit the edge
+            };
+            if next == b'#' {
+                self.current_direction = self.current_direction.turn_right();
+            } else {
+                self.current_row = row;
+                self.current_col = col;
+                //Encode current position
+                let encoded: u32 = (self.current_row as u32) << 11
+                    | (self.current_col as u32) << 3
+                    | self.current_direction.index() as u32;
+                if position_back_buffer.add(encoded) {
+                    return true;
+                }
+            }
+        }
+    }
+}
+fn find_guard(grid: &Grid<u8>) -> Guard {
+    //Find guard init state
+    // Walk grid to find the '^' character
+    let (current_row, current_col) = grid.find(&b'^').unwrap();
+    // println!("Guard starts at {current_row} {current_col}");
+    Guard {
+        current_row: current_row as i64,
+        current_col: current_col as i64,
+        current_direction: Direction::Up,
+    }
+}
+fn part_a(grid: &Grid<u8>) -> i64 {
+    find_guard(grid).walk_matrix_count_steps(grid.clone(), None)
+}
+fn part_b(grid: &Grid<u8>) -> i64 {
+    let guard = find_guard(grid);
+
+    // For all positions, that are empty, test putting obstruction there
+    let empty_spots: Vec<(usize, usize)> = grid.find_all(&b'.').collect();
+    empty_spots
+        .par_iter()
+        .map(|&pos| {
+            let mut new_grid = grid.clone();
+            new_grid[pos] = b'#';
+            let mut new_guard = guard;
+            new_guard.walk_matrix_does_loop(&new_grid)
+        })
+        .filter(|x| *x)
+        .count() as i64 // Count how many are true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day6::solve_file("test.txt", Part::A);
+        assert_eq!(results, 41);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day6::solve_file("input.txt", Part::A);
+        assert_eq!(results, 4826);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day6::solve_file("test.txt", Part::B);
+        assert_eq!(results, 6);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day6::solve_file("input.txt", Part::B);
+        assert_eq!(results, 1721);
+    }
+}
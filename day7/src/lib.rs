@@ -0,0 +1,310 @@
+use itertools::Itertools;
+use rayon::prelude::*;
+use shared::{Answer, ParseError, Solution};
+use std::fmt::Display;
+
+mod operation;
+pub use operation::*;
+
+shared::register_day!(7, Day7);
+
+pub struct Day7;
+impl Solution for Day7 {
+    type Input = Vec<Equation>;
+    type Config = Day7Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(Equation::try_from)
+            .collect()
+    }
+    fn part_a(input: &Self::Input, config: &Day7Config) -> Answer {
+        part_a(input, config).into()
+    }
+    fn part_b(input: &Self::Input, config: &Day7Config) -> Answer {
+        part_b(input, config).into()
+    }
+}
+pub struct Day7Config {
+    pub operations_a: Vec<Operations>,
+    pub operations_b: Vec<Operations>,
+}
+impl Default for Day7Config {
+    fn default() -> Self {
+        Self {
+            operations_a: ALL_OPERATIONS_A.to_vec(),
+            operations_b: ALL_OPERATIONS_B.to_vec(),
+        }
+    }
+}
+impl Day7Config {
+    // Each part's operators as a comma separated list of symbols, like "+,*,||,-"
+    pub fn parse(operations_a: &str, operations_b: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            operations_a: parse_operations(operations_a)?,
+            operations_b: parse_operations(operations_b)?,
+        })
+    }
+}
+
+fn part_a(equations: &[Equation], config: &Day7Config) -> i64 {
+    // Count how many are solvable with base operations
+    equations
+        .par_iter()
+        .filter(|x| x.solve(&config.operations_a).is_some())
+        .map(|x| x.test_value)
+        .sum::<i64>()
+}
+fn part_b(equations: &[Equation], config: &Day7Config) -> i64 {
+    // Count how many are solvable with concat
+    equations
+        .par_iter()
+        .filter(|x| x.solve(&config.operations_b).is_some())
+        .map(|x| x.test_value)
+        .sum::<i64>()
+}
+
+pub struct Equation {
+    test_value: i64,
+    inputs: Vec<i64>,
+}
+impl Equation {
+    pub fn test_value(&self) -> i64 {
+        self.test_value
+    }
+    pub fn inputs(&self) -> &[i64] {
+        &self.inputs
+    }
+    // Left to right, None if any step has no answer
+    pub fn evaluate(&self, operations: &[Operations]) -> Option<i64> {
+        assert_eq!(operations.len() + 1, self.inputs.len());
+        operations
+            .iter()
+            .zip(&self.inputs[1..])
+            .try_fold(self.inputs[0], |total, (op, &next)| op.apply(total, next))
+    }
+
+    // Works back from the test value, undoing the last operation at each step to find what the numbers
+    // before it have to come to. Most operations can only be undone one way or not at all (the value
+    // has to divide, or end in the right digits), so very few branches survive
+    pub fn solve(&self, operations: &[Operations]) -> Option<Vec<Operations>> {
+        let prune_negative = operations.iter().all(|op| op.keeps_non_negative())
+            && self.inputs.iter().all(|&i| i >= 0);
+        let mut found = Vec::with_capacity(self.inputs.len() - 1);
+        if self.solve_prefix(
+            operations,
+            self.inputs.len() - 1,
+            self.test_value,
+            prune_negative,
+            &mut found,
+        ) {
+            // Found back to front
+            found.reverse();
+            Some(found)
+        } else {
+            None
+        }
+    }
+    // Whether inputs[..=last] can come to target, pushing the operations used from the right
+    fn solve_prefix(
+        &self,
+        operations: &[Operations],
+        last: usize,
+        target: i64,
+        prune_negative: bool,
+        found: &mut Vec<Operations>,
+    ) -> bool {
+        if prune_negative && target < 0 {
+            return false;
+        }
+        if last == 0 {
+            return self.inputs[0] == target;
+        }
+        for &op in operations {
+            found.push(op);
+            let solved = match op.inverse(target, self.inputs[last]) {
+                Inverse::Any => self.evaluate_prefix(operations, last - 1, found),
+                Inverse::Values(values) => values.into_iter().flatten().any(|value| {
+                    self.solve_prefix(operations, last - 1, value, prune_negative, found)
+                }),
+            };
+            if solved {
+                return true;
+            }
+            found.pop();
+        }
+        false
+    }
+    // Any way of getting inputs[..=last] to come to something, when the rest doesn't care what
+    fn evaluate_prefix(
+        &self,
+        operations: &[Operations],
+        last: usize,
+        found: &mut Vec<Operations>,
+    ) -> bool {
+        let mut chosen = Vec::with_capacity(last);
+        let works = self.evaluate_forwards(operations, last, self.inputs[0], &mut chosen);
+        if works {
+            found.extend(chosen.into_iter().rev());
+        }
+        works
+    }
+    fn evaluate_forwards(
+        &self,
+        operations: &[Operations],
+        last: usize,
+        total: i64,
+        chosen: &mut Vec<Operations>,
+    ) -> bool {
+        if chosen.len() == last {
+            return true;
+        }
+        let next = self.inputs[chosen.len() + 1];
+        for &op in operations {
+            if let Some(total) = op.apply(total, next) {
+                chosen.push(op);
+                if self.evaluate_forwards(operations, last, total, chosen) {
+                    return true;
+                }
+                chosen.pop();
+            }
+        }
+        false
+    }
+}
+impl Display for Equation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.test_value, self.inputs.iter().format(" "))
+    }
+}
+// Writes the equation out with the operators in, `190: 10 * 19`
+pub fn format_solution(equation: &Equation, operations: &[Operations]) -> String {
+    let mut written = format!("{}: {}", equation.test_value, equation.inputs[0]);
+    for (op, next) in operations.iter().zip(&equation.inputs[1..]) {
+        written += &format!(" {op} {next}");
+    }
+    written
+}
+impl TryFrom<&str> for Equation {
+    type Error = ParseError;
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        // Split test_value from the front by ':' and then split the rest by ' '
+        let (test_value, inputs) = input
+            .split_once(":")
+            .ok_or_else(|| ParseError::new(format!("Missing `:` in equation `{input}`")))?;
+        let test_value = test_value.parse()?;
+        let inputs: Vec<i64> = inputs
+            .split(" ")
+            .filter(|l| !l.is_empty())
+            .map(|x| x.parse())
+            .collect::<Result<_, _>>()?;
+        if inputs.is_empty() {
+            return Err(ParseError::new(format!("No numbers in equation `{input}`")));
+        }
+        Ok(Equation { test_value, inputs })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{create_all_possible_operations, Part};
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day7::solve_file("test.txt", Part::A);
+        assert_eq!(results, 3749);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day7::solve_file("input.txt", Part::A);
+        assert_eq!(results, 303766880536);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day7::solve_file("test.txt", Part::B);
+        assert_eq!(results, 11387);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day7::solve_file("input.txt", Part::B);
+        assert_eq!(results, 337041851384440);
+    }
+    #[test]
+    fn test_operator_sequences() {
+        let equation = Equation::try_from("7290: 6 8 6 15").unwrap();
+        let ops = equation.solve(&ALL_OPERATIONS_B).unwrap();
+        assert_eq!(format_solution(&equation, &ops), "7290: 6 * 8 || 6 * 15");
+        assert_eq!(equation.solve(&ALL_OPERATIONS_A), None);
+
+        // Multiplying by 0 at the end means the rest can be anything
+        let equation = Equation::try_from("0: 5 3 0").unwrap();
+        let ops = equation.solve(&ALL_OPERATIONS_A).unwrap();
+        assert_eq!(equation.evaluate(&ops), Some(0));
+    }
+    #[test]
+    fn test_custom_operations() {
+        let extended = Day7Config::parse("-, /", "+,^").unwrap();
+        let equation = Equation::try_from("3: 20 5 1").unwrap();
+        let ops = equation.solve(&extended.operations_a).unwrap();
+        assert_eq!(format_solution(&equation, &ops), "3: 20 / 5 - 1");
+        // Subtraction can go below zero, so that can't be pruned
+        let equation = Equation::try_from("-4: 1 5").unwrap();
+        let ops = equation.solve(&extended.operations_a).unwrap();
+        assert_eq!(format_solution(&equation, &ops), "-4: 1 - 5");
+        assert_eq!(equation.solve(&ALL_OPERATIONS_B), None);
+        let equation = Equation::try_from("9: 1 2 2").unwrap();
+        let ops = equation.solve(&extended.operations_b).unwrap();
+        assert_eq!(format_solution(&equation, &ops), "9: 1 + 2 ^ 2");
+
+        // Operators read at runtime
+        let config = Day7Config::parse("+,*", "+,*,||,-").unwrap();
+        let equation = Equation::try_from("54: 5 5 1").unwrap();
+        let ops = equation.solve(&config.operations_b).unwrap();
+        assert_eq!(format_solution(&equation, &ops), "54: 5 || 5 - 1");
+        assert_eq!(equation.solve(&ALL_OPERATIONS_B), None);
+        // Part B without concatenate is just part A
+        let config = Day7Config::parse("+,*", "+, *").unwrap();
+        let results = Day7::solve_file_with("test.txt", Part::B, &config);
+        assert_eq!(results, 3749);
+        assert_eq!(
+            Day7Config::parse("+,%", "+").err().unwrap().to_string(),
+            "Unknown operator `%`"
+        );
+        assert!(Day7Config::parse("+,,*", "+").is_err());
+    }
+    #[test]
+    fn test_matches_brute_force() {
+        // Every combination of operators, the way this used to be solved
+        let everything = [
+            Operations::Add,
+            Operations::Multiply,
+            Operations::Concatenate,
+            Operations::Subtract,
+            Operations::Divide,
+            Operations::Exponent,
+        ];
+        let input = std::fs::read_to_string("test.txt").unwrap();
+        for equation in Day7::parse(&input).unwrap() {
+            for test_value in [equation.test_value, 1, 0, -4, 27, 100] {
+                let equation = Equation {
+                    test_value,
+                    inputs: equation.inputs.clone(),
+                };
+                let brute_force =
+                    create_all_possible_operations(&everything, equation.inputs.len() - 1).any(
+                        |ops| {
+                            let ops: Vec<Operations> = ops.into_iter().copied().collect();
+                            equation.evaluate(&ops) == Some(test_value)
+                        },
+                    );
+                let solved = equation.solve(&everything);
+                assert_eq!(solved.is_some(), brute_force, "{equation}");
+                if let Some(ops) = solved {
+                    assert_eq!(equation.evaluate(&ops), Some(test_value));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// The operators that can go between an equation's numbers, always applied left to right
+// Part A only uses add and multiply, B adds concatenate, the rest can be switched on through the config
+use std::{fmt::Display, str::FromStr};
+
+use count_digits::CountDigits;
+use shared::ParseError;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Operations {
+    Add,
+    Multiply,
+    Concatenate,
+    Subtract,
+    Divide,   // Only when it divides exactly
+    Exponent, // Never to a negative power
+}
+pub const ALL_OPERATIONS_A: [Operations; 2] = [Operations::Add, Operations::Multiply];
+pub const ALL_OPERATIONS_B: [Operations; 3] = [
+    Operations::Add,
+    Operations::Multiply,
+    Operations::Concatenate,
+];
+
+// Values the left hand side could have been, given the result and the right hand side
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Inverse {
+    Any, // The right hand side decides the result on its own, like multiplying by 0
+    Values([Option<i64>; 2]),
+}
+impl Inverse {
+    fn none() -> Self {
+        Inverse::Values([None, None])
+    }
+    fn one(value: Option<i64>) -> Self {
+        Inverse::Values([value, None])
+    }
+}
+
+impl Operations {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Operations::Add => "+",
+            Operations::Multiply => "*",
+            Operations::Concatenate => "||",
+            Operations::Subtract => "-",
+            Operations::Divide => "/",
+            Operations::Exponent => "^",
+        }
+    }
+    // None when there's no answer, or it doesn't fit in an i64
+    pub fn apply(&self, lhs: i64, rhs: i64) -> Option<i64> {
+        match self {
+            Operations::Add => lhs.checked_add(rhs),
+            Operations::Multiply => lhs.checked_mul(rhs),
+            Operations::Concatenate => {
+                //Shift the total by the number of digits in the next number
+                let shift = concatenate_shift(rhs)?;
+                lhs.checked_mul(shift)?.checked_add(rhs)
+            }
+            Operations::Subtract => lhs.checked_sub(rhs),
+            Operations::Divide => {
+                if rhs != 0 && lhs.checked_rem(rhs)? == 0 {
+                    lhs.checked_div(rhs)
+                } else {
+                    None
+                }
+            }
+            Operations::Exponent => lhs.checked_pow(u32::try_from(rhs).ok()?),
+        }
+    }
+    // Undoes the operation, every value returned gives the result back through apply
+    pub fn inverse(&self, result: i64, rhs: i64) -> Inverse {
+        match self {
+            Operations::Add => Inverse::one(result.checked_sub(rhs)),
+            Operations::Subtract => Inverse::one(result.checked_add(rhs)),
+            Operations::Multiply => match (result, rhs) {
+                (0, 0) => Inverse::Any,
+                (_, 0) => Inverse::none(),
+                _ if result.checked_rem(rhs) == Some(0) => Inverse::one(result.checked_div(rhs)),
+                _ => Inverse::none(),
+            },
+            Operations::Divide => Inverse::one(result.checked_mul(rhs).filter(|_| rhs != 0)),
+            Operations::Concatenate => {
+                // The result has to end in the digits of the right hand side
+                let Some(shift) = concatenate_shift(rhs) else {
+                    return Inverse::none();
+                };
+                match result.checked_sub(rhs) {
+                    Some(rest) if rest % shift == 0 => Inverse::one(Some(rest / shift)),
+                    _ => Inverse::none(),
+                }
+            }
+            Operations::Exponent => match rhs {
+                0 if result == 1 => Inverse::Any,
+                ..=0 => Inverse::none(),
+                1 => Inverse::one(Some(result)),
+                _ => {
+                    let Some(root) = integer_root(result.unsigned_abs(), rhs as u32) else {
+                        return Inverse::none();
+                    };
+                    let root = root as i64;
+                    let candidates = if rhs % 2 == 0 {
+                        [Some(root), Some(-root).filter(|&r| r != root)]
+                    } else {
+                        [Some(root * result.signum()), None]
+                    };
+                    Inverse::Values(
+                        candidates.map(|c| c.filter(|&c| self.apply(c, rhs) == Some(result))),
+                    )
+                }
+            },
+        }
+    }
+    // Whether it can only give negative numbers when given one, so the search can skip negative totals
+    pub fn keeps_non_negative(&self) -> bool {
+        !matches!(self, Operations::Subtract)
+    }
+}
+fn concatenate_shift(rhs: i64) -> Option<i64> {
+    if rhs < 0 {
+        return None;
+    }
+    10_i64.checked_pow(rhs.count_digits() as u32)
+}
+// The exact root, if there is one
+fn integer_root(value: u64, power: u32) -> Option<u64> {
+    let guess = (value as f64).powf(1.0 / power as f64).round() as u64;
+    (guess.saturating_sub(1)..=guess + 1).find(|r| r.checked_pow(power) == Some(value))
+}
+
+impl Display for Operations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.symbol())
+    }
+}
+impl FromStr for Operations {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Operations::Add,
+            Operations::Multiply,
+            Operations::Concatenate,
+            Operations::Subtract,
+            Operations::Divide,
+            Operations::Exponent,
+        ]
+        .into_iter()
+        .find(|op| op.symbol() == s)
+        .ok_or_else(|| ParseError::new(format!("Unknown operator `{s}`")))
+    }
+}
+
+// A comma separated list of operator symbols, like "+,*,||"
+pub fn parse_operations(list: &str) -> Result<Vec<Operations>, ParseError> {
+    list.split(',')
+        .map(|symbol| symbol.trim().parse())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse_round_trips() {
+        let ops = ["+", "*", "||", "-", "/", "^"].map(|s| s.parse::<Operations>().unwrap());
+        for op in ops {
+            for lhs in -30..=30 {
+                for rhs in -4..=12 {
+                    let Some(result) = op.apply(lhs, rhs) else {
+                        continue;
+                    };
+                    match op.inverse(result, rhs) {
+                        Inverse::Any => {}
+                        Inverse::Values(values) => {
+                            assert!(values.contains(&Some(lhs)), "{lhs} {op} {rhs} = {result}");
+                            for value in values.into_iter().flatten() {
+                                assert_eq!(op.apply(value, rhs), Some(result));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(Operations::Concatenate.apply(15, 6), Some(156));
+        assert_eq!(
+            Operations::Concatenate.inverse(156, 56),
+            Inverse::one(Some(1))
+        );
+        assert_eq!(Operations::Exponent.inverse(-8, 3), Inverse::one(Some(-2)));
+        assert!("%".parse::<Operations>().is_err());
+    }
+}
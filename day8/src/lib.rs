@@ -1,53 +1,56 @@
 use std::collections::HashMap;
 
-use array2d::Array2D;
 use itertools::Itertools;
 use rayon::prelude::*;
-use shared::read_file_to_grid;
+use shared::{parse_grid, Answer, Grid, ParseError, Solution};
 
-fn main() {
-    let t_a = std::thread::spawn(|| {
-        println!("PART A: {}", part_a("input.txt"));
-    });
-    let t_b = std::thread::spawn(|| {
-        println!("PART B: {}", part_b("input.txt"));
-    });
-    t_a.join().unwrap();
-    t_b.join().unwrap();
+shared::register_day!(8, Day8);
+
+pub struct Day8;
+impl Solution for Day8 {
+    type Input = Grid<u8>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
 }
-fn part_a(path: &str) -> i64 {
-    let grid = read_file_to_grid(path);
-    let antennas = all_unqiue_antennas_with_more_than_one(&grid);
+fn part_a(grid: &Grid<u8>) -> i64 {
+    let antennas = all_unqiue_antennas_with_more_than_one(grid);
     // For each antenna, find the antinodes
     let all_antinodes: Vec<(i64, i64)> = antennas
         .par_iter()
-        .map(|antenna| find_all_antinodes(&grid, *antenna))
+        .map(|antenna| find_all_antinodes(grid, *antenna))
         .flatten()
         .collect();
     // Count number of unqiue antinodes
     all_antinodes.iter().unique().count() as i64
 }
-fn part_b(path: &str) -> i64 {
-    let grid = read_file_to_grid(path);
-    let antennas = all_unqiue_antennas_with_more_than_one(&grid);
+fn part_b(grid: &Grid<u8>) -> i64 {
+    let antennas = all_unqiue_antennas_with_more_than_one(grid);
     // For each antenna, find the antinodes
     let all_antinodes: Vec<(i64, i64)> = antennas
         .par_iter()
-        .map(|antenna| find_all_lined_nodes(&grid, *antenna))
+        .map(|antenna| find_all_lined_nodes(grid, *antenna))
         .flatten()
         .collect();
     // Count number of unqiue antinodes
     all_antinodes.iter().unique().count() as i64
 }
-fn find_location_of_antenna(data: &Array2D<u8>, antenna: u8) -> Vec<(i64, i64)> {
+fn find_location_of_antenna(data: &Grid<u8>, antenna: u8) -> Vec<(i64, i64)> {
     //Find all rows,columns in data that have antenna
-    data.enumerate_row_major()
-        .filter(|(_, &x)| x == antenna)
-        .map(|(i, _)| (i.0 as i64, i.1 as i64))
+    data.find_all(&antenna)
+        .map(|(row, col)| (row as i64, col as i64))
         .collect()
 }
 
-fn find_all_lined_nodes(data: &Array2D<u8>, antenna: u8) -> Vec<(i64, i64)> {
+fn find_all_lined_nodes(data: &Grid<u8>, antenna: u8) -> Vec<(i64, i64)> {
     //The signal only applies its nefarious effect at specific antinodes based on the resonant frequencies of the antennas.
     // In particular, an antinode occurs at any point that is perfectly in line with two antennas of the same frequency -
     // but only when one of the antennas is twice as far away as the other.
@@ -91,7 +94,7 @@ fn find_all_lined_nodes(data: &Array2D<u8>, antenna: u8) -> Vec<(i64, i64)> {
         .flatten()
         .collect::<Vec<(i64, i64)>>()
 }
-fn find_all_antinodes(data: &Array2D<u8>, antenna: u8) -> Vec<(i64, i64)> {
+fn find_all_antinodes(data: &Grid<u8>, antenna: u8) -> Vec<(i64, i64)> {
     //The signal only applies its nefarious effect at specific antinodes based on the resonant frequencies of the antennas.
     // In particular, an antinode occurs at any point that is perfectly in line with two antennas of the same frequency -
     // but only when one of the antennas is twice as far away as the other.
@@ -126,14 +129,12 @@ fn find_all_antinodes(data: &Array2D<u8>, antenna: u8) -> Vec<(i64, i64)> {
     // Filter out the points that are outside the bounds of the grid
     a1_jump
         .chain(a2_jump)
-        .filter(|(x, y)| {
-            (*x >= 0 && *x < data.num_rows() as i64) && (*y >= 0 && *y < data.num_columns() as i64)
-        })
+        .filter(|&(x, y)| data.in_bounds(x, y))
         .collect()
 }
-fn all_unqiue_antennas_with_more_than_one(data: &Array2D<u8>) -> Vec<u8> {
+fn all_unqiue_antennas_with_more_than_one(data: &Grid<u8>) -> Vec<u8> {
     let mut items: HashMap<u8, usize> = HashMap::with_capacity(128);
-    data.elements_row_major_iter().for_each(|x| {
+    data.iter().for_each(|x| {
         let count = items.entry(*x).or_insert(0);
         *count += 1;
     });
@@ -149,25 +150,26 @@ fn all_unqiue_antennas_with_more_than_one(data: &Array2D<u8>) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared::Part;
 
     #[test]
     fn test_part_a_demo() {
-        let results = part_a("test.txt");
+        let results = Day8::solve_file("test.txt", Part::A);
         assert_eq!(results, 14);
     }
     #[test]
     fn test_part_a_real() {
-        let results = part_a("input.txt");
+        let results = Day8::solve_file("input.txt", Part::A);
         assert_eq!(results, 396);
     }
     #[test]
     fn test_part_b_demo() {
-        let results = part_b("test.txt");
+        let results = Day8::solve_file("test.txt", Part::B);
         assert_eq!(results, 34);
     }
     #[test]
     fn test_part_b_real() {
-        let results = part_b("input.txt");
+        let results = Day8::solve_file("input.txt", Part::B);
         assert_eq!(results, 1200);
     }
 }
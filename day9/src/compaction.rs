@@ -0,0 +1,230 @@
+// Pluggable policies for where files get moved to, for comparing allocators on the same disk
+// These are general rather than fast, the two puzzle parts have their own quicker passes
+use std::{cmp::Reverse, collections::BTreeMap, fmt::Display};
+
+use crate::{FileSpan, FreeSpan};
+
+// Free space while compacting, gaps either side of a file that moves away are joined back up
+#[derive(Debug, Clone, Default)]
+pub struct FreeList {
+    gaps: BTreeMap<usize, usize>, // Start to length
+}
+impl FreeList {
+    pub fn new(gaps: impl IntoIterator<Item = FreeSpan>) -> Self {
+        let mut free = Self::default();
+        for gap in gaps {
+            free.release(gap.start, gap.len);
+        }
+        free
+    }
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = FreeSpan> + '_ {
+        self.gaps
+            .iter()
+            .map(|(&start, &len)| FreeSpan { start, len })
+    }
+    // Gaps that start before the position
+    pub fn before(&self, position: usize) -> impl DoubleEndedIterator<Item = FreeSpan> + '_ {
+        self.gaps
+            .range(..position)
+            .map(|(&start, &len)| FreeSpan { start, len })
+    }
+    // Gaps that start at the position or after it
+    pub fn after(&self, position: usize) -> impl DoubleEndedIterator<Item = FreeSpan> + '_ {
+        self.gaps
+            .range(position..)
+            .map(|(&start, &len)| FreeSpan { start, len })
+    }
+    // Marks blocks as used, false if they weren't all free
+    fn take(&mut self, start: usize, len: usize) -> bool {
+        let Some((&gap_start, &gap_len)) = self.gaps.range(..=start).next_back() else {
+            return false;
+        };
+        if start + len > gap_start + gap_len {
+            return false;
+        }
+        self.gaps.remove(&gap_start);
+        if start > gap_start {
+            self.gaps.insert(gap_start, start - gap_start);
+        }
+        if gap_start + gap_len > start + len {
+            self.gaps
+                .insert(start + len, gap_start + gap_len - start - len);
+        }
+        true
+    }
+    fn release(&mut self, mut start: usize, mut len: usize) {
+        if len == 0 {
+            return;
+        }
+        if let Some((&before, &before_len)) = self.gaps.range(..start).next_back() {
+            if before + before_len == start {
+                self.gaps.remove(&before);
+                start = before;
+                len += before_len;
+            }
+        }
+        if let Some(after_len) = self.gaps.remove(&(start + len)) {
+            len += after_len;
+        }
+        self.gaps.insert(start, len);
+    }
+}
+
+pub trait CompactionStrategy {
+    fn name(&self) -> &'static str;
+    // Where the file should start, or None to leave it where it is
+    // The file has to fit entirely inside one of the free gaps, anywhere else is a `PlacementError`
+    fn place(&mut self, file: &FileSpan, free: &FreeList) -> Option<usize>;
+}
+
+// A strategy put a file somewhere that isn't free
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacementError {
+    pub strategy: &'static str,
+    pub file_id: i64,
+    pub start: usize,
+    pub len: usize,
+}
+impl Display for PlacementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} placed file {} at {}..{}, outside the free space",
+            self.strategy,
+            self.file_id,
+            self.start,
+            self.start + self.len
+        )
+    }
+}
+
+// Leftmost gap it fits in, what part B does
+pub struct FirstFit;
+impl CompactionStrategy for FirstFit {
+    fn name(&self) -> &'static str {
+        "first-fit"
+    }
+    fn place(&mut self, file: &FileSpan, free: &FreeList) -> Option<usize> {
+        free.before(file.start)
+            .find(|gap| gap.len >= file.len)
+            .map(|gap| gap.start)
+    }
+}
+// Tightest gap to the left, leaving the big ones for big files
+pub struct BestFit;
+impl CompactionStrategy for BestFit {
+    fn name(&self) -> &'static str {
+        "best-fit"
+    }
+    fn place(&mut self, file: &FileSpan, free: &FreeList) -> Option<usize> {
+        free.before(file.start)
+            .filter(|gap| gap.len >= file.len)
+            .min_by_key(|gap| (gap.len, gap.start))
+            .map(|gap| gap.start)
+    }
+}
+// Biggest gap to the left, so what's left over is still useful
+pub struct WorstFit;
+impl CompactionStrategy for WorstFit {
+    fn name(&self) -> &'static str {
+        "worst-fit"
+    }
+    fn place(&mut self, file: &FileSpan, free: &FreeList) -> Option<usize> {
+        free.before(file.start)
+            .filter(|gap| gap.len >= file.len)
+            .max_by_key(|gap| (gap.len, Reverse(gap.start)))
+            .map(|gap| gap.start)
+    }
+}
+// First fit, but carrying on from wherever the last file went, wrapping round to the start
+#[derive(Default)]
+pub struct NextFit {
+    cursor: usize,
+}
+impl CompactionStrategy for NextFit {
+    fn name(&self) -> &'static str {
+        "next-fit"
+    }
+    fn place(&mut self, file: &FileSpan, free: &FreeList) -> Option<usize> {
+        let cursor = self.cursor.min(file.start);
+        let start = free
+            .after(cursor)
+            .chain(free.before(cursor))
+            .filter(|gap| gap.start < file.start)
+            .find(|gap| gap.len >= file.len)
+            .map(|gap| gap.start)?;
+        self.cursor = start + file.len;
+        Some(start)
+    }
+}
+// Packs files against the end of the disk instead, into the rightmost gap after them
+pub struct MoveToEnd;
+impl CompactionStrategy for MoveToEnd {
+    fn name(&self) -> &'static str {
+        "move-to-end"
+    }
+    fn place(&mut self, file: &FileSpan, free: &FreeList) -> Option<usize> {
+        free.after(file.start + file.len)
+            .rev()
+            .find(|gap| gap.len >= file.len)
+            .map(|gap| gap.start + gap.len - file.len)
+    }
+}
+
+pub fn all_strategies() -> Vec<Box<dyn CompactionStrategy>> {
+    vec![
+        Box::new(FirstFit),
+        Box::new(BestFit),
+        Box::new(WorstFit),
+        Box::new(NextFit::default()),
+        Box::new(MoveToEnd),
+    ]
+}
+
+// Offers every file once, highest id first, and moves the ones the strategy finds a place for
+// Adds up the blocks moved, and stops at the first bad placement keeping the files moved so far
+pub(crate) fn compact_files(
+    files: &mut [FileSpan],
+    free: &mut FreeList,
+    strategy: &mut dyn CompactionStrategy,
+    blocks_moved: &mut usize,
+) -> Result<(), PlacementError> {
+    let mut order: Vec<usize> = (0..files.len()).collect();
+    order.sort_by_key(|&i| Reverse(files[i].id));
+    for i in order {
+        let file = files[i];
+        let Some(start) = strategy.place(&file, free) else {
+            continue;
+        };
+        if !free.take(start, file.len) {
+            files.sort_unstable_by_key(|file| file.start);
+            return Err(PlacementError {
+                strategy: strategy.name(),
+                file_id: file.id,
+                start,
+                len: file.len,
+            });
+        }
+        free.release(file.start, file.len);
+        files[i].start = start;
+        *blocks_moved += file.len;
+    }
+    files.sort_unstable_by_key(|file| file.start);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_list_merges() {
+        let mut free =
+            FreeList::new([FreeSpan { start: 2, len: 3 }, FreeSpan { start: 8, len: 1 }]);
+        assert!(!free.take(3, 3));
+        assert!(free.take(3, 1));
+        free.release(5, 3);
+        let gaps: Vec<(usize, usize)> = free.iter().map(|g| (g.start, g.len)).collect();
+        assert_eq!(gaps, [(2, 1), (4, 5)]);
+    }
+}
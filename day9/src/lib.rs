@@ -0,0 +1,471 @@
+use shared::{Answer, ParseError, Solution};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fmt::Display,
+};
+
+mod compaction;
+pub use compaction::*;
+
+shared::register_day!(9, Day9);
+
+pub struct Day9;
+impl Solution for Day9 {
+    type Input = BlockWiseDisk;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        BlockWiseDisk::parse(input)
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+}
+// A run of blocks all belonging to one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileSpan {
+    pub id: i64,
+    pub start: usize,
+    pub len: usize,
+}
+// A run of free blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreeSpan {
+    pub start: usize,
+    pub len: usize,
+}
+// The disk as runs of blocks rather than every block, so its size doesn't matter, only how many runs
+// Free space is whatever the files don't cover
+#[derive(Debug, Clone)]
+pub struct BlockWiseDisk {
+    files: Vec<FileSpan>, // Sorted by start, a file may be split over several spans
+    len: usize,           // Total blocks, used or not
+    blocks_moved: usize,  // By every compaction so far
+}
+// How broken up the disk is, for comparing compaction strategies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FragmentationReport {
+    pub free_runs: usize,
+    pub largest_free_run: usize,
+    pub files_split: usize, // Files spread over more than one span
+    pub blocks_moved: usize,
+}
+impl Display for FragmentationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} free runs, largest {}, {} files split, {} blocks moved",
+            self.free_runs, self.largest_free_run, self.files_split, self.blocks_moved
+        )
+    }
+}
+impl BlockWiseDisk {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut files = Vec::new();
+        let mut position = 0;
+        for (i, char) in input.trim().chars().enumerate() {
+            let size = char
+                .to_digit(10)
+                .ok_or_else(|| ParseError::new(format!("Invalid disk map digit `{char}`")))?
+                as usize;
+            // Even digits are files, odd ones the free space after them
+            if i % 2 == 0 && size > 0 {
+                files.push(FileSpan {
+                    id: (i / 2) as i64,
+                    start: position,
+                    len: size,
+                });
+            }
+            position += size;
+        }
+        Ok(BlockWiseDisk {
+            files,
+            len: position,
+            blocks_moved: 0,
+        })
+    }
+    // Back to the digit format, None if it can't be written that way: files out of id order or
+    // split up, or runs longer than a digit
+    pub fn to_disk_map(&self) -> Option<String> {
+        let Some(max_id) = self.files.iter().map(|file| file.id).max() else {
+            return Some(self.len.to_string()).filter(|_| self.len == 0);
+        };
+        let mut by_id: Vec<Option<FileSpan>> = vec![None; max_id as usize + 1];
+        for file in &self.files {
+            if by_id[file.id as usize].replace(*file).is_some() {
+                return None;
+            }
+        }
+        let digit = |n: usize| char::from_digit(u32::try_from(n).ok()?, 10);
+        let mut map = String::new();
+        let mut position = 0;
+        for file in by_id {
+            // Zero sized files aren't kept, they sit wherever the last file ended
+            let file = file.unwrap_or(FileSpan {
+                id: -1,
+                start: position,
+                len: 0,
+            });
+            if file.start < position {
+                return None;
+            }
+            if !map.is_empty() {
+                map.push(digit(file.start - position)?);
+            } else if file.start > 0 {
+                return None;
+            }
+            map.push(digit(file.len)?);
+            position = file.start + file.len;
+        }
+        if self.len > position {
+            map.push(digit(self.len - position)?);
+        }
+        Some(map)
+    }
+    pub fn files(&self) -> &[FileSpan] {
+        &self.files
+    }
+    pub fn free_spans(&self) -> Vec<FreeSpan> {
+        let mut free = Vec::new();
+        let mut position = 0;
+        for file in self.files.iter().chain(&[FileSpan {
+            id: -1,
+            start: self.len,
+            len: 0,
+        }]) {
+            if file.start > position {
+                free.push(FreeSpan {
+                    start: position,
+                    len: file.start - position,
+                });
+            }
+            position = file.start + file.len;
+        }
+        free
+    }
+    #[allow(unused)]
+    pub fn print(&self) {
+        let mut line = ".".repeat(self.len).into_bytes();
+        for file in &self.files {
+            let digit = b'0' + (file.id % 10) as u8;
+            line[file.start..file.start + file.len].fill(digit);
+        }
+        println!("Disk> `{}`", String::from_utf8(line).unwrap())
+    }
+    pub fn fragmentation(&self) -> FragmentationReport {
+        let free = self.free_spans();
+        let mut spans_per_file: HashMap<i64, usize> = HashMap::new();
+        for file in &self.files {
+            *spans_per_file.entry(file.id).or_default() += 1;
+        }
+        FragmentationReport {
+            free_runs: free.len(),
+            largest_free_run: free.iter().map(|gap| gap.len).max().unwrap_or(0),
+            files_split: spans_per_file.values().filter(|&&spans| spans > 1).count(),
+            blocks_moved: self.blocks_moved,
+        }
+    }
+    // Moves whole files wherever the strategy says, each one at most once
+    // A strategy that misplaces a file stops the run there, with the files moved so far kept
+    pub fn compact(
+        &mut self,
+        strategy: &mut dyn CompactionStrategy,
+    ) -> Result<FragmentationReport, PlacementError> {
+        let mut free = FreeList::new(self.free_spans());
+        compact_files(&mut self.files, &mut free, strategy, &mut self.blocks_moved)?;
+        Ok(self.fragmentation())
+    }
+    pub fn get_checksum(&self) -> i64 {
+        // To calculate the checksum, add up the result of multiplying each of these blocks' position with the file ID number it contains.
+        // The leftmost block is in position 0. If a block contains free space, skip it instead.
+        // A span covers start..start+len, so its positions add up to len*start + the triangle number below len
+        self.files
+            .iter()
+            .map(|file| {
+                let (start, len) = (file.start as i64, file.len as i64);
+                file.id * (len * start + len * (len - 1) / 2)
+            })
+            .sum()
+    }
+    pub fn defragment_chunkwise(&mut self) {
+        // Move all blocks from the right to the left-most empty spot
+        // Walk the gaps from the left, filling each with the tail of the last file until they meet
+        let gaps = self.free_spans();
+        let mut files = std::mem::take(&mut self.files);
+        let mut moved = Vec::new();
+        'gaps: for mut gap in gaps {
+            while gap.len > 0 {
+                let Some(last) = files.last_mut() else {
+                    break 'gaps;
+                };
+                if last.start < gap.start {
+                    //DOne, all blocks moved left
+                    break 'gaps;
+                }
+                let taken = gap.len.min(last.len);
+                self.blocks_moved += taken;
+                moved.push(FileSpan {
+                    id: last.id,
+                    start: gap.start,
+                    len: taken,
+                });
+                gap.start += taken;
+                gap.len -= taken;
+                last.len -= taken;
+                if last.len == 0 {
+                    files.pop();
+                }
+            }
+        }
+        files.extend(moved);
+        files.sort_unstable_by_key(|file| file.start);
+        self.files = files;
+    }
+    pub fn defragment_filewise(&mut self) {
+        // Attempt to move each block once, to the leftmost gap that it fits in
+        // Work from highest file id -> lowest
+        // Gaps are kept in a min-heap of starts for each size, so the leftmost gap a file fits in is
+        // the smallest start across the heaps for its size and up
+        let free = self.free_spans();
+        let largest = free.iter().map(|gap| gap.len).max().unwrap_or(0);
+        let mut gaps_by_size: Vec<BinaryHeap<Reverse<usize>>> =
+            vec![BinaryHeap::new(); largest + 1];
+        for gap in free {
+            gaps_by_size[gap.len].push(Reverse(gap.start));
+        }
+
+        let mut order: Vec<usize> = (0..self.files.len()).collect();
+        order.sort_by_key(|&i| Reverse(self.files[i].id));
+        for i in order {
+            let file = self.files[i];
+            let best = (file.len..gaps_by_size.len())
+                .filter_map(|size| {
+                    gaps_by_size[size]
+                        .peek()
+                        .map(|&Reverse(start)| (start, size))
+                })
+                .min();
+            let Some((start, size)) = best.filter(|&(start, _)| start < file.start) else {
+                continue;
+            };
+            gaps_by_size[size].pop();
+            if size > file.len {
+                gaps_by_size[size - file.len].push(Reverse(start + file.len));
+            }
+            // Space left behind is to the right of every file still to move, so nothing can use it
+            self.files[i].start = start;
+            self.blocks_moved += file.len;
+        }
+        self.files.sort_unstable_by_key(|file| file.start);
+    }
+}
+
+// Runs every strategy on a copy of the disk, giving each one's checksum and report
+pub fn compare_strategies(disk: &BlockWiseDisk) -> Vec<(&'static str, i64, FragmentationReport)> {
+    all_strategies()
+        .into_iter()
+        .map(|mut strategy| {
+            let mut disk = disk.clone();
+            let report = disk
+                .compact(strategy.as_mut())
+                .expect("Built in strategies only use free space");
+            (strategy.name(), disk.get_checksum(), report)
+        })
+        .collect()
+}
+
+fn part_a(disk: &BlockWiseDisk) -> i64 {
+    let mut disk = disk.clone();
+    // disk.print();
+    disk.defragment_chunkwise();
+    disk.get_checksum()
+}
+fn part_b(disk: &BlockWiseDisk) -> i64 {
+    let mut disk = disk.clone();
+    // disk.print();
+    disk.defragment_filewise();
+    disk.get_checksum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = Day9::solve_file("test.txt", Part::A);
+        assert_eq!(results, 1928);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = Day9::solve_file("input.txt", Part::A);
+        assert_eq!(results, 6225730762521);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = Day9::solve_file("test.txt", Part::B);
+        assert_eq!(results, 2858);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = Day9::solve_file("input.txt", Part::B);
+        assert_eq!(results, 6250605700557);
+    }
+
+    // Every block spelled out, moved one at a time, as the puzzle describes it
+    fn blocks(map: &str) -> Vec<i64> {
+        map.bytes()
+            .enumerate()
+            .flat_map(|(i, digit)| {
+                let id = if i % 2 == 0 { (i / 2) as i64 } else { -1 };
+                std::iter::repeat_n(id, (digit - b'0') as usize)
+            })
+            .collect()
+    }
+    fn block_checksum(blocks: &[i64]) -> i64 {
+        blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, &id)| id >= 0)
+            .map(|(pos, &id)| pos as i64 * id)
+            .sum()
+    }
+    fn blockwise_chunkwise(mut blocks: Vec<i64>) -> i64 {
+        let (mut left, mut right) = (0, blocks.len() - 1);
+        while left < right {
+            if blocks[left] != -1 {
+                left += 1;
+            } else if blocks[right] == -1 {
+                right -= 1;
+            } else {
+                blocks.swap(left, right);
+            }
+        }
+        block_checksum(&blocks)
+    }
+    fn blockwise_filewise(mut blocks: Vec<i64>) -> i64 {
+        let max_id = *blocks.iter().max().unwrap();
+        for id in (0..=max_id).rev() {
+            let Some(start) = blocks.iter().position(|&b| b == id) else {
+                continue;
+            };
+            let len = blocks.iter().filter(|&&b| b == id).count();
+            if let Some(gap) = (0..start).find(|&g| blocks[g..g + len].iter().all(|&b| b == -1)) {
+                for i in 0..len {
+                    blocks.swap(start + i, gap + i);
+                }
+            }
+        }
+        block_checksum(&blocks)
+    }
+    #[test]
+    fn test_strategies() {
+        let disk = BlockWiseDisk::parse("2333133121414131402").unwrap();
+        let results: HashMap<&str, (i64, FragmentationReport)> = compare_strategies(&disk)
+            .into_iter()
+            .map(|(name, checksum, report)| (name, (checksum, report)))
+            .collect();
+        // First fit is part B
+        assert_eq!(results["first-fit"].0, 2858);
+        assert_eq!(
+            results["first-fit"].1,
+            FragmentationReport {
+                free_runs: 6,
+                largest_free_run: 5,
+                files_split: 0,
+                blocks_moved: 8
+            }
+        );
+        let original = disk.fragmentation();
+        assert_eq!((original.free_runs, original.largest_free_run), (8, 3));
+        for (name, (_, report)) in &results {
+            assert_eq!(report.files_split, 0, "{name} split a file");
+        }
+        // Files only ever move right, so every position in the checksum can only grow
+        assert!(results["move-to-end"].0 > disk.get_checksum());
+        assert!(results["move-to-end"].1.blocks_moved > 0);
+
+        let mut chunked = disk.clone();
+        chunked.defragment_chunkwise();
+        let report = chunked.fragmentation();
+        // 0099811188827773336446555566, 8 and 6 end up in two pieces
+        assert_eq!((report.free_runs, report.files_split), (1, 2));
+    }
+    #[test]
+    fn test_disk_map_round_trip() {
+        for map in ["2333133121414131402", "12345", "10203", "0"] {
+            let disk = BlockWiseDisk::parse(map).unwrap();
+            let written = disk.to_disk_map().unwrap();
+            let reparsed = BlockWiseDisk::parse(&written).unwrap();
+            assert_eq!(reparsed.files(), disk.files(), "{map} -> {written}");
+            assert_eq!(reparsed.get_checksum(), disk.get_checksum());
+        }
+        assert_eq!(
+            BlockWiseDisk::parse("12345")
+                .unwrap()
+                .to_disk_map()
+                .unwrap(),
+            "12345"
+        );
+        // First fit keeps files in one piece, but out of order
+        let mut disk = BlockWiseDisk::parse("2333133121414131402").unwrap();
+        disk.compact(&mut FirstFit).unwrap();
+        assert_eq!(disk.to_disk_map(), None);
+        // Unless the order happens to survive
+        let mut disk = BlockWiseDisk::parse("111").unwrap();
+        disk.compact(&mut FirstFit).unwrap();
+        assert_eq!(disk.to_disk_map().unwrap(), "1011");
+        // The digit format always starts with a file
+        let mut disk = BlockWiseDisk::parse("11111").unwrap();
+        disk.compact(&mut MoveToEnd).unwrap();
+        let starts: Vec<usize> = disk.files().iter().map(|file| file.start).collect();
+        assert_eq!(starts, [2, 3, 4]);
+        assert_eq!(disk.to_disk_map(), None);
+    }
+    #[test]
+    fn test_misbehaving_strategy() {
+        // Puts every file at the very start, whatever's there
+        struct Squash;
+        impl CompactionStrategy for Squash {
+            fn name(&self) -> &'static str {
+                "squash"
+            }
+            fn place(&mut self, _file: &FileSpan, _free: &FreeList) -> Option<usize> {
+                Some(0)
+            }
+        }
+        let mut disk = BlockWiseDisk::parse("12345").unwrap();
+        let before = disk.clone();
+        let error = disk.compact(&mut Squash).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "squash placed file 2 at 0..5, outside the free space"
+        );
+        // Nothing had moved yet, so nothing did
+        assert_eq!(disk.files(), before.files());
+        assert_eq!(disk.fragmentation().blocks_moved, 0);
+    }
+    #[test]
+    fn test_matches_blockwise() {
+        // Pseudo random maps, zero sized files and gaps included
+        let mut seed = 12345_u64;
+        for _ in 0..20 {
+            let map: String = (0..301)
+                .map(|_| {
+                    seed = seed
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    (b'0' + (seed >> 33) as u8 % 10) as char
+                })
+                .collect();
+            let disk = BlockWiseDisk::parse(&map).unwrap();
+            assert_eq!(part_a(&disk), blockwise_chunkwise(blocks(&map)));
+            assert_eq!(part_b(&disk), blockwise_filewise(blocks(&map)));
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Registration of each day's solutions, so the `aoc` runner can dispatch to them
+use crate::{ParseError, TimedRun, VisualizeResult};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub default_input: &'static str, // input.txt inside the day's own crate
+    pub run: fn(&str, &[Part]) -> Result<TimedRun, ParseError>, // Takes the input file contents
+    pub visualize: fn(&str, Part, &Path) -> VisualizeResult,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+pub const ALL_PARTS: [Part; 2] = [Part::A, Part::B];
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::A => f.pad("A"),
+            Part::B => f.pad("B"),
+        }
+    }
+}
+
+// Exposes a `pub const DAY` from the calling crate for the given `Solution`,
+// pointing its default input at that crate's input.txt
+#[macro_export]
+macro_rules! register_day {
+    ($number:expr, $solution:ty) => {
+        pub const DAY: $crate::Day = $crate::Day {
+            number: $number,
+            default_input: concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"),
+            run: $crate::run_timed::<$solution>,
+            visualize: $crate::run_visualize::<$solution>,
+        };
+    };
+}
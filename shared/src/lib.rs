@@ -1,42 +1,81 @@
-use std::fs::read_to_string;
-use std::io;
 mod matrix;
 pub use matrix::*;
 mod combos;
 pub use combos::*;
 mod matrix_direction;
 pub use matrix_direction::*;
+mod day;
+pub use day::*;
+mod parse_error;
+pub use parse_error::*;
+mod solution;
+pub use solution::*;
+pub mod pathfinding;
+pub mod regions;
+pub mod viz;
 
 pub fn read_whitespace_separated_numbers_by_column(
     file_path: &str,
     expected_width: usize,
-) -> io::Result<Vec<Vec<i64>>> {
-    // Read in the file, for each line split on whitespace and parse each number as i64
+) -> Result<Vec<Vec<i64>>, ParseError> {
+    parse_whitespace_separated_numbers_by_column(&read_input_file(file_path)?, expected_width)
+        .map_err(|e| e.with_file(file_path))
+}
+pub fn parse_whitespace_separated_numbers_by_column(
+    input: &str,
+    expected_width: usize,
+) -> Result<Vec<Vec<i64>>, ParseError> {
+    // For each line split on whitespace and parse each number as i64
     let mut columns = vec![Vec::new(); expected_width];
 
-    for line in read_to_string(file_path)?.lines() {
-        let cols: Vec<i64> = line
-            .split_whitespace()
-            .map(|num| num.parse::<i64>().unwrap())
-            .collect();
-        for (i, col) in cols.iter().enumerate() {
-            columns[i].push(*col);
+    for (line_number, line) in numbered_lines(input) {
+        let mut width = 0;
+        for (column, token) in tokens_with_columns(line) {
+            if width == expected_width {
+                return Err(ParseError::at(
+                    line_number,
+                    column,
+                    token,
+                    format!("Expected only {expected_width} numbers on the line"),
+                ));
+            }
+            columns[width].push(parse_token(token, line_number, column)?);
+            width += 1;
         }
+        if width < expected_width {
+            return Err(ParseError::at(
+                line_number,
+                line.chars().count() + 1,
+                line,
+                format!("Expected {expected_width} numbers on the line, found {width}"),
+            ));
+        }
+    }
+    if columns.first().is_some_and(|c| c.is_empty()) {
+        return Err(ParseError::new("Empty input"));
     }
     Ok(columns)
 }
 
-pub fn read_whitespace_separated_numbers_by_row(file_path: &str) -> io::Result<Vec<Vec<i64>>> {
-    // Read in the file, for each line split on whitespace and parse each number as i64
-
-    Ok(read_to_string(file_path)?
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|num| num.parse::<i64>().unwrap())
+pub fn read_whitespace_separated_numbers_by_row(
+    file_path: &str,
+) -> Result<Vec<Vec<i64>>, ParseError> {
+    parse_whitespace_separated_numbers_by_row(&read_input_file(file_path)?)
+        .map_err(|e| e.with_file(file_path))
+}
+pub fn parse_whitespace_separated_numbers_by_row(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    // For each line split on whitespace and parse each number as i64
+    let rows = numbered_lines(input)
+        .map(|(line_number, line)| {
+            tokens_with_columns(line)
+                .map(|(column, token)| parse_token(token, line_number, column))
                 .collect()
         })
-        .collect())
+        .collect::<Result<Vec<Vec<i64>>, _>>()?;
+    if rows.is_empty() {
+        return Err(ParseError::new("Empty input"));
+    }
+    Ok(rows)
 }
 #[cfg(test)]
 mod tests {
@@ -68,4 +107,28 @@ mod tests {
             ]
         );
     }
+    #[test]
+    fn test_parse_by_column_reports_bad_token() {
+        let error = parse_whitespace_separated_numbers_by_column("1 2\n3 x4\n", 2).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), Some(3)));
+        assert_eq!(error.token.as_deref(), Some("x4"));
+    }
+    #[test]
+    fn test_parse_by_column_rejects_ragged_lines() {
+        let error = parse_whitespace_separated_numbers_by_column("1 2\r\n3\r\n", 2).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), Some(2)));
+        let error = parse_whitespace_separated_numbers_by_column("1 2 3\n", 2).unwrap_err();
+        assert_eq!((error.line, error.column), (Some(1), Some(5)));
+    }
+    #[test]
+    fn test_parse_rejects_empty_input() {
+        assert!(parse_whitespace_separated_numbers_by_column("", 2).is_err());
+        assert!(parse_whitespace_separated_numbers_by_row("").is_err());
+    }
+    #[test]
+    fn test_read_reports_file() {
+        let error =
+            read_whitespace_separated_numbers_by_row("./src/test_data/missing.txt").unwrap_err();
+        assert_eq!(error.file.as_deref(), Some("./src/test_data/missing.txt"));
+    }
 }
@@ -1,59 +1,308 @@
-use core::str;
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
 
 use array2d::Array2D;
 
-pub fn read_file_to_grid(path: &str) -> Array2D<u8> {
-    let file_contents = std::fs::read_to_string(path).unwrap();
-    let lines: Vec<&str> = file_contents.lines().collect();
-    let filtered_file = file_contents.replace("\n", "").replace("\r", "");
+use crate::{numbered_lines, read_input_file, ParseError, ALL_DIRECTIONS, ALL_DIRECTIONS_8};
 
-    Array2D::from_row_major(filtered_file.as_bytes(), lines[0].len(), lines.len()).unwrap()
-}
+// Positions are always (row, column), counted from the top left
+pub type Position = (usize, usize);
 
-pub fn read_file_to_num_grid(path: &str) -> Array2D<i64> {
-    let file_contents = std::fs::read_to_string(path).unwrap();
-    let lines: Vec<&str> = file_contents.lines().collect();
-    let filtered_file = file_contents.replace("\n", "").replace("\r", "");
-    let values: Vec<i64> = filtered_file
-        .as_bytes()
-        .iter()
-        .map(|&a| (a - b'0') as i64)
-        .collect();
-    Array2D::from_row_major(&values, lines[0].len(), lines.len()).unwrap()
+// A rectangular grid of cells, wrapping Array2D with the lookups the puzzles keep needing
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Array2D<T>,
 }
 
-pub fn rotate_array(mut grid: Array2D<u8>) -> Array2D<u8> {
-    //Rotate the array 90 deg to the right
-    // Do this by transposing the array, then reversing the rows
-    assert_eq!(grid.row_len(), grid.column_len());
+impl<T> Grid<T> {
+    pub fn num_rows(&self) -> usize {
+        self.cells.num_rows()
+    }
+    pub fn num_columns(&self) -> usize {
+        self.cells.num_columns()
+    }
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        self.cells.get(pos.0, pos.1)
+    }
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        self.cells.get_mut(pos.0, pos.1)
+    }
+    // Lookup that treats anything off the edge (including negatives) as missing
+    pub fn get_signed(&self, row: i64, col: i64) -> Option<&T> {
+        self.cells
+            .get(usize::try_from(row).ok()?, usize::try_from(col).ok()?)
+    }
+    pub fn in_bounds(&self, row: i64, col: i64) -> bool {
+        self.get_signed(row, col).is_some()
+    }
+    // Move from a position by (rows, columns), if that stays inside the grid
+    pub fn offset(&self, pos: Position, delta: (i64, i64)) -> Option<Position> {
+        let row = usize::try_from(pos.0 as i64 + delta.0).ok()?;
+        let col = usize::try_from(pos.1 as i64 + delta.1).ok()?;
+        (row < self.num_rows() && col < self.num_columns()).then_some((row, col))
+    }
+    // Up, down, left, right; skipping any that fall off the grid
+    pub fn neighbours4(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        ALL_DIRECTIONS
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d.delta()))
+    }
+    // As neighbours4, plus the diagonals
+    pub fn neighbours8(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        ALL_DIRECTIONS_8
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d.delta()))
+    }
+    // All cells with their position, in row major order
+    pub fn enumerate(&self) -> impl DoubleEndedIterator<Item = (Position, &T)> + Clone {
+        self.cells.enumerate_row_major()
+    }
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + Clone {
+        self.cells.elements_row_major_iter()
+    }
+    // Panics if the row is outside the grid
+    pub fn row(&self, row: usize) -> impl DoubleEndedIterator<Item = &T> + Clone {
+        self.cells.row_iter(row).expect("Row outside of grid")
+    }
+    // Panics if the column is outside the grid
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + Clone {
+        self.cells.column_iter(col).expect("Column outside of grid")
+    }
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        self.cells.rows_iter()
+    }
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        let cells: Vec<U> = self.iter().map(f).collect();
+        Grid {
+            cells: Array2D::from_iter_row_major(
+                cells.into_iter(),
+                self.num_rows(),
+                self.num_columns(),
+            )
+            .unwrap(),
+        }
+    }
+}
+impl<T: PartialEq> Grid<T> {
+    // First matching cell in row major order
+    pub fn find(&self, value: &T) -> Option<Position> {
+        self.find_all(value).next()
+    }
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a {
+        self.enumerate()
+            .filter(move |(_, v)| *v == value)
+            .map(|(pos, _)| pos)
+    }
+}
+impl<T: Clone> Grid<T> {
+    pub fn filled_with(value: T, num_rows: usize, num_columns: usize) -> Self {
+        Self {
+            cells: Array2D::filled_with(value, num_rows, num_columns),
+        }
+    }
+    // Panics if there aren't exactly rows * columns elements
+    pub fn from_row_major(elements: &[T], num_rows: usize, num_columns: usize) -> Self {
+        Self {
+            cells: Array2D::from_row_major(elements, num_rows, num_columns)
+                .expect("Grid size does not match the number of elements"),
+        }
+    }
+    // Copy out the rectangle starting at top_left, if it fits inside the grid
+    pub fn sub_grid(
+        &self,
+        top_left: Position,
+        num_rows: usize,
+        num_columns: usize,
+    ) -> Option<Self> {
+        if top_left.0 + num_rows > self.num_rows() || top_left.1 + num_columns > self.num_columns()
+        {
+            return None;
+        }
+        let cells = (top_left.0..top_left.0 + num_rows).flat_map(|r| {
+            (top_left.1..top_left.1 + num_columns).map(move |c| self.cells[(r, c)].clone())
+        });
+        Some(Self {
+            cells: Array2D::from_iter_row_major(cells, num_rows, num_columns).unwrap(),
+        })
+    }
+    // The grid turned 90 degrees clockwise
+    pub fn rotated_right(&self) -> Self {
+        let (rows, cols) = (self.num_rows(), self.num_columns());
+        let cells =
+            (0..cols).flat_map(|r| (0..rows).rev().map(move |c| self.cells[(c, r)].clone()));
+        Self {
+            cells: Array2D::from_iter_row_major(cells, cols, rows).unwrap(),
+        }
+    }
+}
+impl<T> From<Array2D<T>> for Grid<T> {
+    fn from(cells: Array2D<T>) -> Self {
+        Self { cells }
+    }
+}
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+    fn index(&self, pos: Position) -> &T {
+        &self.cells[pos]
+    }
+}
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut T {
+        &mut self.cells[pos]
+    }
+}
 
-    //Transpose
-    for r in 0..grid.num_rows() {
-        for c in 0..r {
-            let tmp = *grid.get(r, c).unwrap();
-            let tmp2 = *grid.get(c, r).unwrap();
-            grid.set(r, c, tmp2).unwrap();
-            grid.set(c, r, tmp).unwrap();
+// How a cell is drawn when printing a grid, one character per cell
+pub trait GridCell {
+    fn as_char(&self) -> char;
+}
+impl GridCell for u8 {
+    fn as_char(&self) -> char {
+        *self as char
+    }
+}
+impl GridCell for char {
+    fn as_char(&self) -> char {
+        *self
+    }
+}
+impl GridCell for bool {
+    fn as_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
         }
     }
-    // Flip each row horizontally
-    for r in 0..grid.num_rows() {
-        for c in 0..grid.num_columns() / 2 {
-            let tmp = *grid.get(r, c).unwrap();
-            grid.set(r, c, *grid.get(r, grid.num_columns() - c - 1).unwrap())
-                .unwrap();
-            grid.set(r, grid.num_columns() - c - 1, tmp).unwrap();
+}
+impl GridCell for i64 {
+    fn as_char(&self) -> char {
+        u32::try_from(*self)
+            .ok()
+            .and_then(|d| char::from_digit(d, 10))
+            .unwrap_or('?')
+    }
+}
+impl<T: GridCell> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell.as_char())?;
+            }
+            writeln!(f)?;
         }
+        Ok(())
     }
-    grid
 }
-pub fn print_array(array: &Array2D<u8>) {
-    println!();
-    for row_iter in array.rows_iter() {
-        for element in row_iter {
-            print!("{}", str::from_utf8(&[*element]).unwrap());
+
+pub fn read_file_to_grid(path: &str) -> Result<Grid<u8>, ParseError> {
+    parse_grid(&read_input_file(path)?).map_err(|e| e.with_file(path))
+}
+pub fn parse_grid(input: &str) -> Result<Grid<u8>, ParseError> {
+    let lines = grid_lines(input)?;
+    let cells: Vec<u8> = lines.iter().flat_map(|line| line.bytes()).collect();
+    Ok(Grid::from_row_major(&cells, lines.len(), lines[0].len()))
+}
+
+pub fn read_file_to_num_grid(path: &str) -> Result<Grid<i64>, ParseError> {
+    parse_num_grid(&read_input_file(path)?).map_err(|e| e.with_file(path))
+}
+pub fn parse_num_grid(input: &str) -> Result<Grid<i64>, ParseError> {
+    let lines = grid_lines(input)?;
+    let mut values = Vec::with_capacity(lines.len() * lines[0].len());
+    for (row, line) in lines.iter().enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            let digit = c.to_digit(10).ok_or_else(|| {
+                ParseError::at(row + 1, column + 1, &c.to_string(), "Expected a digit")
+            })?;
+            values.push(digit as i64);
+        }
+    }
+    Ok(Grid::from_row_major(&values, lines.len(), lines[0].len()))
+}
+
+// The lines of a rectangular grid, ignoring trailing blank lines
+fn grid_lines(input: &str) -> Result<Vec<&str>, ParseError> {
+    let mut lines: Vec<&str> = numbered_lines(input).map(|(_, line)| line).collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let width = match lines.first() {
+        Some(first) if !first.is_empty() => first.len(),
+        _ => return Err(ParseError::new("Empty grid")),
+    };
+    for (row, line) in lines.iter().enumerate() {
+        if line.len() != width {
+            return Err(ParseError::at(
+                row + 1,
+                width.min(line.len()) + 1,
+                line,
+                format!("Expected {width} columns, found {}", line.len()),
+            ));
         }
-        println!();
     }
-    println!();
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid() {
+        let grid = parse_grid("abc\r\ndef\r\n\n").unwrap();
+        assert_eq!((grid.num_rows(), grid.num_columns()), (2, 3));
+        assert_eq!(grid[(1, 0)], b'd');
+    }
+    #[test]
+    fn test_parse_grid_rejects_ragged_and_empty() {
+        let error = parse_grid("abc\nde\nfgh").unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), Some(3)));
+        assert!(parse_grid("").is_err());
+        assert!(parse_grid("\n\n").is_err());
+    }
+    #[test]
+    fn test_parse_num_grid_rejects_non_digits() {
+        assert_eq!(parse_num_grid("12\n34").unwrap()[(1, 1)], 4);
+        let error = parse_num_grid("12\n3.").unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), Some(2)));
+        assert_eq!(error.token.as_deref(), Some("."));
+    }
+    #[test]
+    fn test_grid_lookups() {
+        let grid = parse_grid("S.#\n.#E\n").unwrap();
+        assert_eq!(grid.find(&b'S'), Some((0, 0)));
+        assert_eq!(grid.find(&b'E'), Some((1, 2)));
+        assert_eq!(grid.find(&b'x'), None);
+        assert_eq!(
+            grid.find_all(&b'#').collect::<Vec<_>>(),
+            vec![(0, 2), (1, 1)]
+        );
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(1, 1), Some(&b'#'));
+        assert_eq!(grid.get_signed(2, 0), None);
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), b".#E");
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), b"#E");
+    }
+    #[test]
+    fn test_grid_neighbours() {
+        let grid = Grid::filled_with(0, 3, 4);
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((0, 3)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    }
+    #[test]
+    fn test_grid_sub_grid_rotate_and_display() {
+        let grid = parse_grid("abc\ndef\n").unwrap();
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+        assert_eq!(grid.sub_grid((0, 1), 2, 2).unwrap().to_string(), "bc\nef\n");
+        assert!(grid.sub_grid((1, 1), 2, 2).is_none());
+        assert_eq!(grid.rotated_right().to_string(), "da\neb\nfc\n");
+    }
 }
@@ -1,50 +1,280 @@
-use array2d::Array2D;
+use std::{fmt::Display, str::FromStr};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+use crate::{Grid, ParseError, Position};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub enum Direction {
     Up,
     Down,
     Left,
     Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
 }
 
 impl Direction {
-    pub fn move_point<T>(&self, grid: &Array2D<T>, r: usize, c: usize) -> Option<(usize, usize)> {
+    // Step of (rows, columns) taken when moving this way
+    pub fn delta(&self) -> (i64, i64) {
         match self {
-            Direction::Up => {
-                if r > 0 {
-                    Some((r - 1, c))
-                } else {
-                    None
-                }
-            }
-            Direction::Down => {
-                if r < grid.column_len() - 1 {
-                    Some((r + 1, c))
-                } else {
-                    None
-                }
-            }
-            Direction::Left => {
-                if c > 0 {
-                    Some((r, c - 1))
-                } else {
-                    None
-                }
-            }
-            Direction::Right => {
-                if c < grid.row_len() - 1 {
-                    Some((r, c + 1))
-                } else {
-                    None
-                }
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+    pub fn move_point<T>(&self, grid: &Grid<T>, r: usize, c: usize) -> Option<Position> {
+        grid.offset((r, c), self.delta())
+    }
+    // Position in ALL_DIRECTIONS_8, clockwise from Up, handy for packing into bitsets
+    pub fn index(&self) -> usize {
+        ALL_DIRECTIONS_8.iter().position(|d| d == self).unwrap()
+    }
+    pub fn is_diagonal(&self) -> bool {
+        let (dr, dc) = self.delta();
+        dr != 0 && dc != 0
+    }
+    // Rotate clockwise by 45 degrees
+    pub fn turn_right_45(&self) -> Self {
+        ALL_DIRECTIONS_8[(self.index() + 1) % 8]
+    }
+    // Rotate anti-clockwise by 45 degrees
+    pub fn turn_left_45(&self) -> Self {
+        ALL_DIRECTIONS_8[(self.index() + 7) % 8]
+    }
+    // Rotate clockwise by 90 degrees
+    pub fn turn_right(&self) -> Self {
+        ALL_DIRECTIONS_8[(self.index() + 2) % 8]
+    }
+    // Rotate anti-clockwise by 90 degrees
+    pub fn turn_left(&self) -> Self {
+        ALL_DIRECTIONS_8[(self.index() + 6) % 8]
+    }
+    pub fn opposite(&self) -> Self {
+        ALL_DIRECTIONS_8[(self.index() + 4) % 8]
+    }
+    // Map arrows `^v<>`, only the cardinal directions have one
+    pub fn from_arrow(arrow: u8) -> Option<Self> {
+        match arrow {
+            b'^' => Some(Direction::Up),
+            b'v' => Some(Direction::Down),
+            b'<' => Some(Direction::Left),
+            b'>' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+    pub fn arrow(&self) -> Option<u8> {
+        match self {
+            Direction::Up => Some(b'^'),
+            Direction::Down => Some(b'v'),
+            Direction::Left => Some(b'<'),
+            Direction::Right => Some(b'>'),
+            _ => None,
+        }
+    }
+    // Compass points, `N` is up
+    pub fn compass(&self) -> &'static str {
+        match self {
+            Direction::Up => "N",
+            Direction::Down => "S",
+            Direction::Left => "W",
+            Direction::Right => "E",
+            Direction::UpLeft => "NW",
+            Direction::UpRight => "NE",
+            Direction::DownLeft => "SW",
+            Direction::DownRight => "SE",
+        }
+    }
+}
+// Cardinal directions print as their arrow, diagonals as their compass point
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.arrow() {
+            Some(arrow) => write!(f, "{}", arrow as char),
+            None => write!(f, "{}", self.compass()),
+        }
+    }
+}
+// Accepts either an arrow `^v<>` or a compass point `N`, `NE` ... `NW`
+impl FromStr for Direction {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let [arrow] = s.as_bytes() {
+            if let Some(direction) = Direction::from_arrow(*arrow) {
+                return Ok(direction);
             }
         }
+        ALL_DIRECTIONS_8
+            .into_iter()
+            .find(|d| d.compass().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseError::new(format!("Invalid direction `{s}`")))
     }
 }
+
 pub const ALL_DIRECTIONS: [Direction; 4] = [
     Direction::Down,
     Direction::Left,
     Direction::Right,
     Direction::Up,
 ];
+pub const DIAGONAL_DIRECTIONS: [Direction; 4] = [
+    Direction::UpRight,
+    Direction::DownRight,
+    Direction::DownLeft,
+    Direction::UpLeft,
+];
+// Clockwise, starting from Up
+pub const ALL_DIRECTIONS_8: [Direction; 8] = [
+    Direction::Up,
+    Direction::UpRight,
+    Direction::Right,
+    Direction::DownRight,
+    Direction::Down,
+    Direction::DownLeft,
+    Direction::Left,
+    Direction::UpLeft,
+];
+
+// The six neighbours of a hex in a pointy-topped hex grid, with positions in axial (q, r) coordinates:
+// q goes up to the east, r up to the south east, and the third cube coordinate is -q - r
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+impl HexDirection {
+    // Step of (q, r) taken when moving this way
+    pub fn delta(&self) -> (i64, i64) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+        }
+    }
+    pub fn step(&self, (q, r): (i64, i64)) -> (i64, i64) {
+        let (dq, dr) = self.delta();
+        (q + dq, r + dr)
+    }
+    // Position in ALL_HEX_DIRECTIONS, clockwise from East
+    pub fn index(&self) -> usize {
+        ALL_HEX_DIRECTIONS.iter().position(|d| d == self).unwrap()
+    }
+    // Rotate clockwise by 60 degrees
+    pub fn turn_right(&self) -> Self {
+        ALL_HEX_DIRECTIONS[(self.index() + 1) % 6]
+    }
+    // Rotate anti-clockwise by 60 degrees
+    pub fn turn_left(&self) -> Self {
+        ALL_HEX_DIRECTIONS[(self.index() + 5) % 6]
+    }
+    pub fn opposite(&self) -> Self {
+        ALL_HEX_DIRECTIONS[(self.index() + 3) % 6]
+    }
+    pub fn compass(&self) -> &'static str {
+        match self {
+            HexDirection::East => "E",
+            HexDirection::SouthEast => "SE",
+            HexDirection::SouthWest => "SW",
+            HexDirection::West => "W",
+            HexDirection::NorthWest => "NW",
+            HexDirection::NorthEast => "NE",
+        }
+    }
+}
+impl Display for HexDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.compass())
+    }
+}
+// Accepts a compass point `E`, `SE` ... `NE`, there's no north or south between hexes
+impl FromStr for HexDirection {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ALL_HEX_DIRECTIONS
+            .into_iter()
+            .find(|d| d.compass().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseError::new(format!("Invalid hex direction `{s}`")))
+    }
+}
+// Fewest steps between two hexes
+pub fn hex_distance(a: (i64, i64), b: (i64, i64)) -> i64 {
+    let (dq, dr) = (a.0 - b.0, a.1 - b.1);
+    (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+}
+
+// Clockwise, starting from East
+pub const ALL_HEX_DIRECTIONS: [HexDirection; 6] = [
+    HexDirection::East,
+    HexDirection::SouthEast,
+    HexDirection::SouthWest,
+    HexDirection::West,
+    HexDirection::NorthWest,
+    HexDirection::NorthEast,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpRight.turn_right(), Direction::DownRight);
+        assert_eq!(Direction::Up.turn_right_45(), Direction::UpRight);
+        assert_eq!(Direction::Up.turn_left_45(), Direction::UpLeft);
+        for d in ALL_DIRECTIONS_8 {
+            assert_eq!(d.opposite().opposite(), d);
+            let (dr, dc) = d.delta();
+            assert_eq!(d.opposite().delta(), (-dr, -dc));
+            assert_eq!(d.turn_left().turn_right(), d);
+        }
+    }
+    #[test]
+    fn test_parse() {
+        assert_eq!("^".parse::<Direction>().unwrap(), Direction::Up);
+        assert_eq!(">".parse::<Direction>().unwrap(), Direction::Right);
+        assert_eq!("S".parse::<Direction>().unwrap(), Direction::Down);
+        assert_eq!("nw".parse::<Direction>().unwrap(), Direction::UpLeft);
+        assert!("x".parse::<Direction>().is_err());
+        for d in ALL_DIRECTIONS_8 {
+            assert_eq!(d.to_string().parse::<Direction>().unwrap(), d);
+        }
+    }
+    #[test]
+    fn test_hex_directions() {
+        assert_eq!(HexDirection::East.turn_right(), HexDirection::SouthEast);
+        assert_eq!(HexDirection::East.turn_left(), HexDirection::NorthEast);
+        assert_eq!(HexDirection::NorthWest.opposite(), HexDirection::SouthEast);
+        for d in ALL_HEX_DIRECTIONS {
+            let (dq, dr) = d.delta();
+            assert_eq!(d.opposite().delta(), (-dq, -dr));
+            assert_eq!(d.turn_left().turn_right(), d);
+            // Six turns go all the way round
+            assert_eq!((0..6).fold(d, |d, _| d.turn_right()), d);
+            assert_eq!(hex_distance((0, 0), d.step((0, 0))), 1);
+            assert_eq!(d.to_string().parse::<HexDirection>().unwrap(), d);
+        }
+        assert!("N".parse::<HexDirection>().is_err());
+        // Steps 60 degrees apart land two away, 120 apart is a neighbour
+        let there = HexDirection::East.step(HexDirection::SouthEast.step((0, 0)));
+        assert_eq!(there, (1, 1));
+        assert_eq!(hex_distance((0, 0), there), 2);
+        let back = HexDirection::West.step(HexDirection::NorthEast.step((0, 0)));
+        assert_eq!(back, HexDirection::NorthWest.step((0, 0)));
+        assert_eq!(hex_distance((3, -1), (-2, 4)), 5);
+    }
+}
@@ -0,0 +1,124 @@
+use std::{fmt::Display, num::ParseIntError};
+
+// Returned when puzzle input can't be understood
+// Line and column are 1-based, column counts characters from the start of the line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub token: Option<String>, // The text that could not be parsed
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+            token: None,
+        }
+    }
+    // An error pointing at a token within a line
+    pub fn at(line: usize, column: usize, token: &str, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            column: Some(column),
+            token: Some(token.to_owned()),
+            ..Self::new(message)
+        }
+    }
+    // Errors are built while parsing a string, the file is attached by whoever read it
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file.get_or_insert_with(|| file.to_owned());
+        self
+    }
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Formatted like a compiler location, `file:line:column: message`
+        let location = [
+            self.file.clone(),
+            self.line.map(|l| l.to_string()),
+            self.column.map(|c| c.to_string()),
+        ];
+        let location = location.into_iter().flatten().collect::<Vec<_>>();
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(":"))?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(token) = &self.token {
+            write!(f, " (found `{}`)", token.escape_debug())?;
+        }
+        Ok(())
+    }
+}
+impl std::error::Error for ParseError {}
+
+impl From<ParseIntError> for ParseError {
+    fn from(value: ParseIntError) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+// Reads a whole input file, reporting failures against the file
+pub fn read_input_file(path: &str) -> Result<String, ParseError> {
+    std::fs::read_to_string(path)
+        .map_err(|e| ParseError::new(format!("Failed to read: {e}")).with_file(path))
+}
+
+// Splits a line on whitespace, keeping the 1-based column each token started at
+pub fn tokens_with_columns(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_whitespace().map(move |token| {
+        let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+        (line[..offset].chars().count() + 1, token)
+    })
+}
+
+// Parses one token, reporting its position on failure
+pub fn parse_token<T>(token: &str, line: usize, column: usize) -> Result<T, ParseError>
+where
+    T: std::str::FromStr,
+    T::Err: Display,
+{
+    token
+        .parse()
+        .map_err(|e| ParseError::at(line, column, token, format!("{e}")))
+}
+
+// Input lines, numbered from 1, with any stray carriage return stripped
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_with_location() {
+        let error =
+            ParseError::at(3, 5, "1x", "invalid digit found in string").with_file("input.txt");
+        assert_eq!(
+            error.to_string(),
+            "input.txt:3:5: invalid digit found in string (found `1x`)"
+        );
+        assert_eq!(ParseError::new("Empty input").to_string(), "Empty input");
+    }
+    #[test]
+    fn test_tokens_with_columns() {
+        let tokens: Vec<_> = tokens_with_columns("  12   7 é9").collect();
+        assert_eq!(tokens, vec![(3, "12"), (8, "7"), (10, "é9")]);
+    }
+}
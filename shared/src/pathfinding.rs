@@ -0,0 +1,274 @@
+// Searches over any state type, driven by a successor function returning the states reachable from a state
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+// Fewest steps from start to every reachable state
+pub fn bfs<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert(start.clone(), 0);
+    queue.push_back(start);
+
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state];
+        for next in successors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+// Fewest steps from start to the first state matching is_goal, stopping as soon as it is found
+pub fn bfs_distance<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    seen.insert(start.clone());
+    queue.push_back((start, 0));
+
+    while let Some((state, distance)) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(distance);
+        }
+        for next in successors(&state) {
+            if seen.insert(next.clone()) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    None
+}
+
+// Queue entry, ordered so the BinaryHeap pops the lowest priority first
+struct Entry<S> {
+    priority: i64,
+    state: S,
+}
+impl<S> PartialEq for Entry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+impl<S> Eq for Entry<S> {}
+impl<S> Ord for Entry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+impl<S> PartialOrd for Entry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Result of a Dijkstra search, every state keeps all of its predecessors that reach it at the lowest cost
+pub struct ShortestPaths<S> {
+    costs: HashMap<S, i64>,
+    predecessors: HashMap<S, Vec<S>>,
+}
+impl<S: Clone + Eq + Hash> ShortestPaths<S> {
+    pub fn cost(&self, state: &S) -> Option<i64> {
+        self.costs.get(state).copied()
+    }
+    pub fn costs(&self) -> &HashMap<S, i64> {
+        &self.costs
+    }
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], |p| p.as_slice())
+    }
+    // The cheapest of the goals, along with its cost
+    pub fn cheapest<'a>(&self, goals: impl IntoIterator<Item = &'a S>) -> Option<(&'a S, i64)>
+    where
+        S: 'a,
+    {
+        goals
+            .into_iter()
+            .filter_map(|goal| Some((goal, self.cost(goal)?)))
+            .min_by_key(|(_, cost)| *cost)
+    }
+    // Every state on any lowest cost path from the start to one of the goals (including both ends)
+    pub fn states_on_optimal_paths<'a>(&self, goals: impl IntoIterator<Item = &'a S>) -> HashSet<S>
+    where
+        S: 'a,
+    {
+        let mut on_path = HashSet::new();
+        let mut stack: Vec<S> = goals
+            .into_iter()
+            .filter(|goal| self.costs.contains_key(goal))
+            .cloned()
+            .collect();
+        while let Some(state) = stack.pop() {
+            if on_path.insert(state.clone()) {
+                stack.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+        on_path
+    }
+}
+
+// Lowest cost from start to every reachable state, successors yield (state, cost of the step)
+pub fn dijkstra<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> ShortestPaths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, i64)>,
+{
+    let mut costs = HashMap::new();
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    costs.insert(start.clone(), 0);
+    queue.push(Entry {
+        priority: 0,
+        state: start,
+    });
+
+    while let Some(Entry { priority, state }) = queue.pop() {
+        if priority > costs[&state] {
+            continue; // Already found a cheaper way here
+        }
+        for (next, step_cost) in successors(&state) {
+            let cost = priority + step_cost;
+            match costs.get(&next).map(|&best| cost.cmp(&best)) {
+                Some(Ordering::Greater) => {}
+                Some(Ordering::Equal) => predecessors.entry(next).or_default().push(state.clone()),
+                _ => {
+                    costs.insert(next.clone(), cost);
+                    predecessors.insert(next.clone(), vec![state.clone()]);
+                    queue.push(Entry {
+                        priority: cost,
+                        state: next,
+                    });
+                }
+            }
+        }
+    }
+    ShortestPaths {
+        costs,
+        predecessors,
+    }
+}
+
+// Lowest cost path from start to a goal, guided by a heuristic that must never overestimate the remaining cost
+// Returns the cost and the states along the path, start and goal included
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> i64,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(i64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, i64)>,
+{
+    let mut costs = HashMap::new();
+    let mut came_from: HashMap<S, S> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    costs.insert(start.clone(), 0);
+    queue.push(Entry {
+        priority: heuristic(&start),
+        state: start,
+    });
+
+    while let Some(Entry { priority, state }) = queue.pop() {
+        let cost = costs[&state];
+        if priority > cost + heuristic(&state) {
+            continue; // Stale entry, this state has been improved since
+        }
+        if is_goal(&state) {
+            let mut path = vec![state];
+            while let Some(previous) = came_from.get(path.last().unwrap()) {
+                path.push(previous.clone());
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next.clone(), next_cost);
+                came_from.insert(next.clone(), state.clone());
+                queue.push(Entry {
+                    priority: next_cost + heuristic(&next),
+                    state: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_grid, Grid, Position};
+
+    // S.#
+    // ...
+    // #.E
+    fn demo_grid() -> Grid<u8> {
+        parse_grid("S.#\n...\n#.E\n").unwrap()
+    }
+    fn open_neighbours(grid: &Grid<u8>, pos: Position) -> Vec<Position> {
+        grid.neighbours4(pos).filter(|&p| grid[p] != b'#').collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = demo_grid();
+        let distances = bfs((0, 0), |&p| open_neighbours(&grid, p));
+        assert_eq!(distances.len(), 7);
+        assert_eq!(distances[&(2, 2)], 4);
+        assert_eq!(
+            bfs_distance((0, 0), |&p| open_neighbours(&grid, p), |&p| p == (2, 1)),
+            Some(3)
+        );
+        assert_eq!(
+            bfs_distance((0, 0), |&p| open_neighbours(&grid, p), |&p| p == (0, 2)),
+            None
+        );
+    }
+    #[test]
+    fn test_dijkstra_keeps_every_optimal_path() {
+        let grid = demo_grid();
+        let paths = dijkstra((0, 0), |&p| {
+            open_neighbours(&grid, p).into_iter().map(|n| (n, 1))
+        });
+        assert_eq!(paths.cost(&(2, 1)), Some(3));
+        assert_eq!(paths.predecessors(&(1, 1)).len(), 2);
+        // Both routes around the top left corner are optimal, the right hand column is never on one
+        let on_path = paths.states_on_optimal_paths(&[(2, 1)]);
+        assert_eq!(on_path.len(), 5);
+        assert!(!on_path.contains(&(1, 2)));
+    }
+    #[test]
+    fn test_astar() {
+        let grid = demo_grid();
+        let goal: Position = (2, 2);
+        let (cost, path) = astar(
+            (0, 0),
+            |&p| open_neighbours(&grid, p).into_iter().map(|n| (n, 1)),
+            |&(r, c)| (goal.0.abs_diff(r) + goal.1.abs_diff(c)) as i64,
+            |&p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(path.len(), 5);
+        assert_eq!((path[0], path[4]), ((0, 0), goal));
+    }
+}
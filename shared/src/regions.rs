@@ -0,0 +1,231 @@
+// Connected component labelling, grouping neighbouring cells of equal value into regions
+use crate::{Direction, Grid, Position, ALL_DIRECTIONS};
+
+// Inclusive corners of the smallest rectangle holding a region
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub top_left: Position,
+    pub bottom_right: Position,
+}
+impl BoundingBox {
+    pub fn num_rows(&self) -> usize {
+        self.bottom_right.0 - self.top_left.0 + 1
+    }
+    pub fn num_columns(&self) -> usize {
+        self.bottom_right.1 - self.top_left.1 + 1
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Region {
+    label: usize,
+    cells: Vec<Position>,
+    bounding_box: BoundingBox,
+    mask: Grid<bool>, // Covers the bounding box, true for cells in the region
+}
+impl Region {
+    fn new(label: usize, cells: Vec<Position>) -> Self {
+        let top_left = (
+            cells.iter().map(|p| p.0).min().unwrap(),
+            cells.iter().map(|p| p.1).min().unwrap(),
+        );
+        let bottom_right = (
+            cells.iter().map(|p| p.0).max().unwrap(),
+            cells.iter().map(|p| p.1).max().unwrap(),
+        );
+        let bounding_box = BoundingBox {
+            top_left,
+            bottom_right,
+        };
+        let mut mask =
+            Grid::filled_with(false, bounding_box.num_rows(), bounding_box.num_columns());
+        for &(r, c) in &cells {
+            mask[(r - top_left.0, c - top_left.1)] = true;
+        }
+        Self {
+            label,
+            cells,
+            bounding_box,
+            mask,
+        }
+    }
+    pub fn label(&self) -> usize {
+        self.label
+    }
+    pub fn cells(&self) -> &[Position] {
+        &self.cells
+    }
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn contains(&self, pos: Position) -> bool {
+        self.contains_signed(pos.0 as i64, pos.1 as i64)
+    }
+    fn contains_signed(&self, row: i64, col: i64) -> bool {
+        let (top, left) = self.bounding_box.top_left;
+        self.mask.get_signed(row - top as i64, col - left as i64) == Some(&true)
+    }
+    // Whether the cell has an edge of the region on the given side
+    fn has_edge(&self, (row, col): (i64, i64), side: Direction) -> bool {
+        let (dr, dc) = side.delta();
+        self.contains_signed(row, col) && !self.contains_signed(row + dr, col + dc)
+    }
+    // Number of cell edges between the region and anything else, holes included
+    pub fn perimeter(&self) -> usize {
+        ALL_DIRECTIONS
+            .iter()
+            .map(|&side| {
+                self.cells
+                    .iter()
+                    .filter(|&&(r, c)| self.has_edge((r as i64, c as i64), side))
+                    .count()
+            })
+            .sum()
+    }
+    // Number of straight sides of the fence facing one way, e.g. Up counts the top sides
+    pub fn sides_facing(&self, side: Direction) -> usize {
+        assert!(!side.is_diagonal(), "Sides only face cardinal directions");
+        // Walking along the side, a cell only starts a new side if the one before it didn't share the edge
+        let (ar, ac) = side.turn_left().delta();
+        self.cells
+            .iter()
+            .map(|&(r, c)| (r as i64, c as i64))
+            .filter(|&(r, c)| self.has_edge((r, c), side) && !self.has_edge((r + ar, c + ac), side))
+            .count()
+    }
+    // Straight sides in all four orientations
+    pub fn sides(&self) -> usize {
+        ALL_DIRECTIONS
+            .iter()
+            .map(|&side| self.sides_facing(side))
+            .sum()
+    }
+    // Groups of cells not in the region that are completely surrounded by it
+    // Gaps only need to touch diagonally to join up, as that is enough to slip out between region cells
+    pub fn holes(&self) -> usize {
+        // Pad the mask by a cell all round, so everything outside is joined up through the border
+        let (rows, cols) = (self.mask.num_rows() + 2, self.mask.num_columns() + 2);
+        let mut outside = Grid::filled_with(false, rows, cols);
+        for (pos, &inside) in self.mask.enumerate() {
+            outside[(pos.0 + 1, pos.1 + 1)] = inside;
+        }
+        // `outside` is now true for every cell taken, by the region or by a flood fill
+        fn flood(grid: &mut Grid<bool>, start: Position) {
+            let mut stack = vec![start];
+            grid[start] = true;
+            while let Some(pos) = stack.pop() {
+                let next: Vec<Position> = grid.neighbours8(pos).filter(|&n| !grid[n]).collect();
+                for n in next {
+                    grid[n] = true;
+                    stack.push(n);
+                }
+            }
+        }
+        flood(&mut outside, (0, 0));
+        let mut holes = 0;
+        for r in 0..rows {
+            for c in 0..cols {
+                if !outside[(r, c)] {
+                    holes += 1;
+                    flood(&mut outside, (r, c));
+                }
+            }
+        }
+        holes
+    }
+}
+
+pub struct Regions {
+    pub labels: Grid<usize>, // The region label of every cell, indexes into `regions`
+    pub regions: Vec<Region>,
+}
+impl Regions {
+    pub fn region_at(&self, pos: Position) -> &Region {
+        &self.regions[self.labels[pos]]
+    }
+}
+
+// Label every group of equal cells that touch up, down, left or right, leaving the grid untouched
+pub fn label_regions<T: PartialEq>(grid: &Grid<T>) -> Regions {
+    let mut labels: Grid<Option<usize>> = grid.map(|_| None);
+    let mut regions = Vec::new();
+
+    for (start, value) in grid.enumerate() {
+        if labels[start].is_some() {
+            continue;
+        }
+        let label = regions.len();
+        labels[start] = Some(label);
+        let mut cells = vec![start];
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            for next in grid.neighbours4(pos) {
+                if labels[next].is_none() && grid[next] == *value {
+                    labels[next] = Some(label);
+                    cells.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+        regions.push(Region::new(label, cells));
+    }
+    Regions {
+        labels: labels.map(|label| label.unwrap()),
+        regions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_grid;
+
+    #[test]
+    fn test_region_geometry() {
+        let grid = parse_grid("AAAA\nBBCD\nBBCC\nEEEC\n").unwrap();
+        let regions = label_regions(&grid);
+        assert_eq!(regions.regions.len(), 5);
+        // Area, perimeter, sides for each plant
+        let expected = [
+            ((0, 0), 4, 10, 4),
+            ((1, 0), 4, 8, 4),
+            ((1, 2), 4, 10, 8),
+            ((1, 3), 1, 4, 4),
+            ((3, 0), 3, 8, 4),
+        ];
+        for (pos, area, perimeter, sides) in expected {
+            let region = regions.region_at(pos);
+            assert_eq!(
+                (region.area(), region.perimeter(), region.sides()),
+                (area, perimeter, sides)
+            );
+        }
+        let c = regions.region_at((1, 2));
+        assert_eq!(
+            c.bounding_box(),
+            BoundingBox {
+                top_left: (1, 2),
+                bottom_right: (3, 3)
+            }
+        );
+        assert_eq!(c.sides_facing(Direction::Up), 2);
+        assert_eq!(c.sides_facing(Direction::Left), 2);
+        assert_eq!(c.holes(), 0);
+    }
+    #[test]
+    fn test_region_holes() {
+        let grid = parse_grid("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO\n").unwrap();
+        let regions = label_regions(&grid);
+        assert_eq!(regions.regions.len(), 5);
+        let o = regions.region_at((0, 0));
+        assert_eq!((o.area(), o.perimeter(), o.sides()), (21, 36, 20));
+        assert_eq!(o.holes(), 4);
+        assert_eq!(regions.region_at((1, 1)).holes(), 0);
+        // A gap in the corner lets the middle out diagonally
+        let grid = parse_grid("AAA\nABA\nAAC\n").unwrap();
+        assert_eq!(label_regions(&grid).region_at((0, 0)).holes(), 0);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{read_input_file, viz::Recorder, ParseError, Part};
+use std::{
+    error::Error,
+    fmt::Display,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+// The result of solving one part of a day
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    Coordinate(i64, i64), // Printed as `x,y`, the way the puzzles ask for it
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => f.pad(&value.to_string()),
+            Answer::Text(text) => f.pad(text),
+            Answer::Coordinate(x, y) => f.pad(&format!("{x},{y}")),
+        }
+    }
+}
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Integer(value)
+    }
+}
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Integer(i64::try_from(value).expect("Answer does not fit in an i64"))
+    }
+}
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Integer(i64::try_from(value).expect("Answer does not fit in an i64"))
+    }
+}
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+// Allows tests to compare straight against the expected value
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        matches!(self, Answer::Integer(value) if value == other)
+    }
+}
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::Text(text) => text == other,
+            _ => self.to_string().as_str() == *other,
+        }
+    }
+}
+
+// A single day's puzzle; parsing is split from solving so the input is only read once for both parts
+pub trait Solution {
+    type Input;
+    // Anything the puzzle leaves as a parameter (grid sizes, thresholds); defaults match the real input
+    type Config: Default;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_a(input: &Self::Input, config: &Self::Config) -> Answer;
+    fn part_b(input: &Self::Input, config: &Self::Config) -> Answer;
+    // Optional animation of solving a part, days without one leave it as None
+    fn visualize(_input: &Self::Input, _part: Part, _config: &Self::Config) -> Option<Recorder> {
+        None
+    }
+
+    fn solve(input: &Self::Input, part: Part, config: &Self::Config) -> Answer {
+        match part {
+            Part::A => Self::part_a(input, config),
+            Part::B => Self::part_b(input, config),
+        }
+    }
+    // Read, parse and solve one part of a file with the default config, panicking on failure
+    fn solve_file(path: &str, part: Part) -> Answer {
+        Self::solve_file_with(path, part, &Self::Config::default())
+    }
+    fn solve_file_with(path: &str, part: Part, config: &Self::Config) -> Answer {
+        let input = read_input_file(path)
+            .and_then(|contents| Self::parse(&contents).map_err(|e| e.with_file(path)))
+            .unwrap_or_else(|e| panic!("{e}"));
+        Self::solve(&input, part, config)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TimedRun {
+    pub parse_time: Duration,
+    pub answers: Vec<(Part, Answer, Duration)>,
+}
+
+// Parse once, then solve each requested part, timing every step
+pub fn run_timed<S: Solution>(contents: &str, parts: &[Part]) -> Result<TimedRun, ParseError> {
+    let config = S::Config::default();
+    let start = Instant::now();
+    let input = S::parse(contents)?;
+    let parse_time = start.elapsed();
+
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = S::solve(&input, part, &config);
+            (part, answer, start.elapsed())
+        })
+        .collect();
+    Ok(TimedRun {
+        parse_time,
+        answers,
+    })
+}
+
+// How many frames were written, None when the day doesn't have a visualisation
+pub type VisualizeResult = Result<Option<usize>, Box<dyn Error>>;
+
+// Parse and record a visualisation of one part to `output`
+pub fn run_visualize<S: Solution>(contents: &str, part: Part, output: &Path) -> VisualizeResult {
+    let input = S::parse(contents)?;
+    let Some(recorder) = S::visualize(&input, part, &S::Config::default()) else {
+        return Ok(None);
+    };
+    recorder.save(output)?;
+    Ok(Some(recorder.len()))
+}
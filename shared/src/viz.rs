@@ -0,0 +1,244 @@
+// Records frames of a simulation and writes them out as an animated GIF or a directory of numbered BMPs
+use std::{
+    fs::{self, File},
+    io,
+    path::Path,
+};
+
+use crate::{Grid, Position};
+
+pub type Rgb = [u8; 3];
+
+// Colours are picked by index, index 0 is the background
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colours: Vec<Rgb>,
+}
+impl Palette {
+    // Panics if there are more colours than a GIF can hold
+    pub fn new(colours: &[Rgb]) -> Self {
+        assert!(
+            !colours.is_empty() && colours.len() <= 256,
+            "Palettes hold 1 to 256 colours"
+        );
+        Self {
+            colours: colours.to_vec(),
+        }
+    }
+    // White on black
+    pub fn monochrome() -> Self {
+        Self::new(&[[0x00, 0x00, 0x00], [0xFF, 0xFF, 0xFF]])
+    }
+    pub fn colour(&self, index: u8) -> Rgb {
+        self.colours[index as usize]
+    }
+    pub fn len(&self) -> usize {
+        self.colours.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.colours.is_empty()
+    }
+}
+
+pub struct Recorder {
+    palette: Palette,
+    frames: Vec<Grid<u8>>,     // Palette indices, one per cell
+    skipped: Option<Grid<u8>>, // Latest frame dropped by `every`, kept so the final state is never lost
+    calls: usize,
+    every: usize,
+    scale: usize,
+    delay: u16,
+}
+impl Recorder {
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            frames: Vec::new(),
+            skipped: None,
+            calls: 0,
+            every: 1,
+            scale: 4,
+            delay: 5,
+        }
+    }
+    // Only keep every nth recorded frame (plus the last), for long simulations
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+    // Pick a frame step that keeps roughly max_frames out of total recordings
+    pub fn every_for_total(self, total: usize, max_frames: usize) -> Self {
+        self.every(total.div_ceil(max_frames.max(1)))
+    }
+    // Pixels along each side of a cell
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+    // Time each GIF frame is shown for, in hundredths of a second
+    pub fn delay(mut self, centiseconds: u16) -> Self {
+        self.delay = centiseconds;
+        self
+    }
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+    // Number of frames that will be written
+    pub fn len(&self) -> usize {
+        self.frames.len() + self.skipped.is_some() as usize
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Records a grid, colouring each cell with the palette index returned by `colour`
+    pub fn record_grid<T>(&mut self, grid: &Grid<T>, colour: impl FnMut(&T) -> u8) {
+        self.push(grid.map(colour));
+    }
+    // Records a set of points on an otherwise background frame
+    pub fn record_points<'a>(
+        &mut self,
+        num_rows: usize,
+        num_columns: usize,
+        points: impl IntoIterator<Item = &'a Position>,
+        colour: u8,
+    ) {
+        let mut frame = Grid::filled_with(0, num_rows, num_columns);
+        for &point in points {
+            if let Some(cell) = frame.get_mut(point) {
+                *cell = colour;
+            }
+        }
+        self.push(frame);
+    }
+    fn push(&mut self, frame: Grid<u8>) {
+        assert!(
+            frame.iter().all(|&i| (i as usize) < self.palette.len()),
+            "Frame uses a colour missing from the palette"
+        );
+        if let Some(first) = self.frames.first() {
+            assert_eq!(
+                (first.num_rows(), first.num_columns()),
+                (frame.num_rows(), frame.num_columns()),
+                "Every frame must be the same size"
+            );
+        }
+        if self.calls.is_multiple_of(self.every) {
+            self.frames.push(frame);
+            self.skipped = None;
+        } else {
+            self.skipped = Some(frame);
+        }
+        self.calls += 1;
+    }
+    fn all_frames(&self) -> impl Iterator<Item = &Grid<u8>> {
+        self.frames.iter().chain(self.skipped.as_ref())
+    }
+    // Expand a frame to one palette index per pixel
+    fn pixels(&self, frame: &Grid<u8>) -> Vec<u8> {
+        frame
+            .rows()
+            .flat_map(|row| {
+                let line: Vec<u8> = row
+                    .flat_map(|&i| std::iter::repeat_n(i, self.scale))
+                    .collect();
+                std::iter::repeat_n(line, self.scale).flatten()
+            })
+            .collect()
+    }
+    fn size(&self) -> (usize, usize) {
+        self.all_frames().next().map_or((0, 0), |f| {
+            (f.num_columns() * self.scale, f.num_rows() * self.scale)
+        })
+    }
+
+    // A `.gif` path gets an animation, anything else is made into a directory of numbered frames
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("gif"))
+        {
+            self.save_gif(path)
+        } else {
+            self.save_frames(path)
+        }
+    }
+    pub fn save_gif(&self, path: &Path) -> io::Result<()> {
+        let (width, height) = self.size();
+        let too_big = || io::Error::other("Frames are too big for a GIF");
+        let width = u16::try_from(width).map_err(|_| too_big())?;
+        let height = u16::try_from(height).map_err(|_| too_big())?;
+        let global_palette: Vec<u8> = self.palette.colours.concat();
+
+        let mut encoder = gif::Encoder::new(File::create(path)?, width, height, &global_palette)
+            .map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        for frame in self.all_frames() {
+            let mut gif_frame =
+                gif::Frame::from_indexed_pixels(width, height, self.pixels(frame), None);
+            gif_frame.delay = self.delay;
+            encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+    // Writes frame_00000.bmp, frame_00001.bmp ... into the directory, creating it if needed
+    pub fn save_frames(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let (width, height) = self.size();
+        for (index, frame) in self.all_frames().enumerate() {
+            let mut image = bmp::Image::new(width as u32, height as u32);
+            for (i, &colour) in self.pixels(frame).iter().enumerate() {
+                let [r, g, b] = self.palette.colour(colour);
+                image.set_pixel(
+                    (i % width) as u32,
+                    (i / width) as u32,
+                    bmp::Pixel::new(r, g, b),
+                );
+            }
+            image.save(dir.join(format!("frame_{index:05}.bmp")))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_keeps_every_nth_and_last_frame() {
+        let mut recorder = Recorder::new(Palette::monochrome()).every(3);
+        for i in 0..8 {
+            recorder.record_points(2, 2, &[(i % 2, 0)], 1);
+        }
+        // Frames 0, 3, 6 and the final 7
+        assert_eq!(recorder.len(), 4);
+    }
+    #[test]
+    fn test_pixels_are_scaled() {
+        let mut recorder = Recorder::new(Palette::monochrome()).scale(2);
+        recorder.record_points(1, 2, &[(0, 1)], 1);
+        assert_eq!(recorder.size(), (4, 2));
+        assert_eq!(
+            recorder.pixels(&recorder.frames[0]),
+            vec![0, 0, 1, 1, 0, 0, 1, 1]
+        );
+    }
+    #[test]
+    fn test_save_gif_and_frames() {
+        let dir = std::env::temp_dir().join(format!("shared_viz_test_{}", std::process::id()));
+        let mut recorder = Recorder::new(Palette::monochrome());
+        let grid = crate::parse_grid("#.\n.#\n").unwrap();
+        recorder.record_grid(&grid, |&c| (c == b'#') as u8);
+        recorder.record_grid(&grid, |&c| (c == b'.') as u8);
+
+        recorder.save(&dir.join("frames")).unwrap();
+        assert!(dir.join("frames/frame_00001.bmp").exists());
+        recorder.save(&dir.join("out.gif")).unwrap();
+        let gif_bytes = fs::read(dir.join("out.gif")).unwrap();
+        assert_eq!(&gif_bytes[..6], b"GIF89a");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
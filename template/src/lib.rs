@@ -0,0 +1,54 @@
+use rayon::prelude::*;
+use shared::{Answer, ParseError, Solution};
+
+// Set the day number here, then add the crate to the `aoc` dependencies so the runner picks it up
+shared::register_day!(0, DayN);
+
+pub struct DayN;
+impl Solution for DayN {
+    type Input = String;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_owned())
+    }
+    fn part_a(input: &Self::Input, _config: &()) -> Answer {
+        part_a(input).into()
+    }
+    fn part_b(input: &Self::Input, _config: &()) -> Answer {
+        part_b(input).into()
+    }
+}
+fn part_a(input: &str) -> i64 {
+    0
+}
+fn part_b(input: &str) -> i64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+
+    #[test]
+    fn test_part_a_demo() {
+        let results = DayN::solve_file("test.txt", Part::A);
+        assert_eq!(results, 0);
+    }
+    #[test]
+    fn test_part_a_real() {
+        let results = DayN::solve_file("input.txt", Part::A);
+        assert_eq!(results, 0);
+    }
+    #[test]
+    fn test_part_b_demo() {
+        let results = DayN::solve_file("test.txt", Part::B);
+        assert_eq!(results, 0);
+    }
+    #[test]
+    fn test_part_b_real() {
+        let results = DayN::solve_file("input.txt", Part::B);
+        assert_eq!(results, 0);
+    }
+}